@@ -7,6 +7,8 @@ use clap_complete::Shell;
 use plugins::colors;
 use plugins::utils;
 use plugins::generate;
+use plugins::export;
+use plugins::gallery;
 
 use std::env;
 use std::path::Path;
@@ -25,13 +27,53 @@ enum Commands {
     },
     Use {
         theme: String,
+
+        /// Recolor the current terminal with OSC escape sequences
+        #[arg(long)]
+        osc: bool,
+
+        /// Recolor every terminal you have open under /dev/pts
+        #[arg(long)]
+        all_ptys: bool,
     },
     List,
 
+    Try {
+        theme: String,
+
+        /// Revert automatically after this long (e.g. 30s, 5m)
+        #[arg(long = "for", value_name = "DURATION")]
+        duration: Option<String>,
+    },
+
     Preview {
         theme: String,
+
+        #[arg(long, value_parser = ["code", "shell", "diff", "ls", "log"])]
+        sample: Option<String>,
     },
     
+    ExportPreview {
+        theme: String,
+
+        #[arg(long, value_parser = ["svg", "png"], default_value = "svg")]
+        format: String,
+
+        /// Output file (defaults to card.<format>)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Variant to render (defaults to the first one)
+        #[arg(long)]
+        variant: Option<String>,
+    },
+
+    Gallery {
+        /// Directory to write index.html into
+        #[arg(short, long, default_value = "gallery")]
+        output: String,
+    },
+
     Generate {
         #[arg(required = false)]
         name: Option<String>,
@@ -54,7 +96,7 @@ fn main() {
     
     match &cli.command {
         Commands::Pull { link } => {
-            let repo_name = link.split('/').last().unwrap();
+            let repo_name = link.split('/').next_back().unwrap();
             let path = format!("{}/.config/themey/themes/{}", home, repo_name);
             
             utils::prep_dir(&path);
@@ -76,20 +118,41 @@ fn main() {
                 println!("-> {}", d.blue().italic());
             }
         },
-        Commands::Use { theme } => {
-            match colors::apply_theme(&theme, &home) {
+        Commands::Use { theme, osc, all_ptys } => {
+            match colors::apply_theme(theme, &home, *osc, *all_ptys) {
                 Ok(_) => {},
                 Err(e) => eprintln!("Failed to apply theme: {}", e),
             }
         },
         
-        Commands::Preview { theme } => {
-            match colors::preview_theme_rgb(&theme, &home) {
+        Commands::Try { theme, duration } => {
+            match colors::try_theme(theme, &home, duration.as_deref()) {
+                Ok(_) => {},
+                Err(e) => eprintln!("Failed to try theme: {}", e),
+            }
+        },
+
+        Commands::Preview { theme, sample } => {
+            match colors::preview_theme_rgb(theme, &home, sample.as_deref()) {
                 Ok(_) => {},
                 Err(e) => eprintln!("Failed to preview theme: {}", e),
             }
         },
 
+        Commands::ExportPreview { theme, format, output, variant } => {
+            match export::export_preview(theme, &home, format, output.as_deref(), variant.as_deref()) {
+                Ok(_) => {},
+                Err(e) => eprintln!("Failed to export preview: {}", e),
+            }
+        },
+
+        Commands::Gallery { output } => {
+            match gallery::build_gallery(&home, output) {
+                Ok(_) => {},
+                Err(e) => eprintln!("Failed to build gallery: {}", e),
+            }
+        },
+
         Commands::Generate { name } => {
             match generate::create_theme_package(name.as_deref()) {
                 Ok(_) => {},
@@ -98,7 +161,7 @@ fn main() {
         },
 
         Commands::Update { theme } => {
-            match generate::update_theme_package(&theme, &home) {
+            match generate::update_theme_package(theme, &home) {
                 Ok(_) => {},
                 Err(e) => eprintln!("Failed to update theme: {}", e),
             }
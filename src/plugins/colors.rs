@@ -1,10 +1,29 @@
 use super::parser::{parse_metadata, parse_colors, Colors};
+use super::templates::ReloadCmd;
+use super::utils::parse_duration;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::io::{self, Write};
+use std::sync::mpsc;
+use std::thread;
 
-pub fn apply_theme(theme_name: &str, home: &str) -> Result<(), Box<dyn std::error::Error>> {
+// What a target's config files looked like before themey overwrote them,
+// as (path, previous contents) in the order they were written
+struct TargetBackup {
+    files: Vec<(String, Option<Vec<u8>>)>,
+    // Directories that didn't exist before, removed again on revert
+    dirs: Vec<PathBuf>,
+    // Run once the files are restored
+    revert: ReloadCmd,
+}
+
+pub fn apply_theme(theme_name: &str, home: &str, osc: bool, all_ptys: bool) -> Result<(), Box<dyn std::error::Error>> {
+    apply_theme_with_backup(theme_name, home, osc, all_ptys)?;
+    Ok(())
+}
+
+fn apply_theme_with_backup(theme_name: &str, home: &str, osc: bool, all_ptys: bool) -> Result<Vec<TargetBackup>, Box<dyn std::error::Error>> {
     // 1. Parse metadata to know what to generate
     let base_path = format!("{}/.config/themey/themes/{}", home, theme_name);
     let metadata_path = format!("{}/metadata.toml", base_path);
@@ -16,46 +35,217 @@ pub fn apply_theme(theme_name: &str, home: &str) -> Result<(), Box<dyn std::erro
     let colors = parse_colors(&theme_path)?;
     
     // 3. Generate files for each target
+    let mut backups = Vec::new();
     for target in &metadata.targets {
-        match target.as_str() {
-            "kitty" => generate_config("kitty", &colors, home)?,
-            "waybar" => generate_config("waybar", &colors, home)?,
-            "hyprland" => generate_config("hyprland", &colors, home)?,
-            "rofi" => generate_config("rofi", &colors, home)?,
-            "dunst" => generate_config("dunst", &colors, home)?,
-            "foot" => generate_config("foot", &colors, home)?,
-            "neovim" => generate_config("neovim", &colors, home)?,
-            "gtk" => generate_config("gtk", &colors, home)?,
-            _ => eprintln!("Unknown target: {}", target),
+        let result = match target.as_str() {
+            "kitty" => generate_config("kitty", &colors, home),
+            "waybar" => generate_config("waybar", &colors, home),
+            "hyprland" => generate_config("hyprland", &colors, home),
+            "rofi" => generate_config("rofi", &colors, home),
+            "dunst" => generate_config("dunst", &colors, home),
+            "foot" => generate_config("foot", &colors, home),
+            "neovim" => generate_config("neovim", &colors, home),
+            "gtk" => generate_config("gtk", &colors, home),
+            "alacritty" => generate_config("alacritty", &colors, home),
+            "wezterm" => generate_config("wezterm", &colors, home),
+            "ghostty" => generate_config("ghostty", &colors, home),
+            "tmux" => generate_config("tmux", &colors, home),
+            "sway" => generate_config("sway", &colors, home),
+            "i3" => generate_config("i3", &colors, home),
+            "xresources" => generate_config("xresources", &colors, home),
+            "mako" => generate_config("mako", &colors, home),
+            "swaync" => generate_config("swaync", &colors, home),
+            "polybar" => generate_config("polybar", &colors, home),
+            "eww" => generate_config("eww", &colors, home),
+            "helix" => generate_config("helix", &colors, home),
+            "vim" => generate_config("vim", &colors, home),
+            "vscode" => generate_config("vscode", &colors, home),
+            "emacs" => generate_config("emacs", &colors, home),
+            "bat" => generate_config("bat", &colors, home),
+            "dircolors" => generate_config("dircolors", &colors, home),
+            "fzf" => generate_config("fzf", &colors, home),
+            "starship" => generate_config("starship", &colors, home),
+            "fish" => generate_config("fish", &colors, home),
+            "btop" => generate_config("btop", &colors, home),
+            "lazygit" => generate_config("lazygit", &colors, home),
+            "yazi" => generate_config("yazi", &colors, home),
+            "zathura" => generate_config("zathura", &colors, home),
+            "qt" => generate_config("qt", &colors, home),
+            "firefox" => generate_config("firefox", &colors, home),
+            "vesktop" => generate_config("vesktop", &colors, home),
+            "obsidian" => generate_config("obsidian", &colors, home),
+            "spicetify" => generate_config("spicetify", &colors, home),
+            "hyprlock" => generate_config("hyprlock", &colors, home),
+            "swaylock" => generate_config("swaylock", &colors, home),
+            "fuzzel" => generate_config("fuzzel", &colors, home),
+            "wofi" => generate_config("wofi", &colors, home),
+            _ => {
+                eprintln!("Unknown target: {}", target);
+                continue;
+            }
+        };
+
+        match result {
+            Ok(backup) => backups.push(backup),
+            Err(e) => {
+                // Don't leave the theme half applied
+                if let Err(restore_err) = restore_backups(&backups) {
+                    eprintln!("{}", restore_err);
+                }
+                return Err(format!("{}: {}", target, e).into());
+            }
         }
     }
     
+    // 4. Recolor running terminals if requested; the files are already in
+    // place, so a failure here only skips the live recolor
+    if (osc || all_ptys)
+        && let Err(e) = super::osc::apply_osc(&colors, all_ptys)
+    {
+        eprintln!("  ⚠ Could not recolor running terminals: {}", e);
+    }
+
     println!("Applied theme '{}' by {}", metadata.name, metadata.author);
-    Ok(())
+    Ok(backups)
 }
 
-fn generate_config(target: &str, colors: &Colors, home: &str) -> Result<(), Box<dyn std::error::Error>> {
+fn generate_config(target: &str, colors: &Colors, home: &str) -> Result<TargetBackup, Box<dyn std::error::Error>> {
     // Get the config details from the template module
-    let (config_path, content, reload_cmd) = super::templates::generate_config_content(target, colors, home)?;
-    
-    // Write the config file
-    if let Some(parent) = Path::new(&config_path).parent() {
-        fs::create_dir_all(parent)?;
+    let output = super::templates::generate_config_content(target, colors, home)?;
+    let mut backup = TargetBackup {
+        files: Vec::new(),
+        dirs: Vec::new(),
+        revert: super::templates::revert_command(target, &output),
+    };
+
+    for (path, content) in output.files {
+        // Keep the previous contents around so the change can be reverted
+        let previous = fs::read(&path).ok();
+
+        // Write the config file, undoing this target's earlier files on failure
+        if let Err(e) = write_config(&path, &content, &mut backup.dirs) {
+            let errors = restore_target(&backup);
+            if errors.is_empty() {
+                return Err(format!("{}: {}", path, e).into());
+            }
+            return Err(format!("{}: {} (could not restore {})", path, e, errors.join(", ")).into());
+        }
+        backup.files.push((path, previous));
+    }
+
+    for note in &output.notes {
+        println!("{}", note);
     }
-    fs::write(&config_path, content)?;
     
     // Reload the application if needed
+    reload(&output.reload);
+    
+    Ok(backup)
+}
+
+fn write_config(path: &str, content: &str, created: &mut Vec<PathBuf>) -> io::Result<()> {
+    let mut dir = Path::new(path).parent();
+    while let Some(d) = dir
+        && !d.as_os_str().is_empty()
+        && !d.exists()
+    {
+        created.push(d.to_path_buf());
+        dir = d.parent();
+    }
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)
+}
+
+// Put a target's files back exactly as they were, newest first. Keeps going
+// past failures and returns a description of each one
+fn restore_target(backup: &TargetBackup) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (path, contents) in backup.files.iter().rev() {
+        let result = match contents {
+            Some(contents) => fs::write(path, contents),
+            None => fs::remove_file(path),
+        };
+        if let Err(e) = result {
+            errors.push(format!("{}: {}", path, e));
+        }
+    }
+
+    // Deepest first; directories something else has written into stay
+    let mut dirs = backup.dirs.clone();
+    dirs.sort_by_key(|d| std::cmp::Reverse(d.components().count()));
+    for dir in dirs {
+        fs::remove_dir(dir).ok();
+    }
+
+    errors
+}
+
+fn restore_backups(backups: &[TargetBackup]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors = Vec::new();
+    for backup in backups.iter().rev() {
+        errors.extend(restore_target(backup));
+        reload(&backup.revert);
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Could not restore {}", errors.join(", ")).into())
+    }
+}
+
+fn reload(reload_cmd: &ReloadCmd) {
     if let Some((cmd, args, name)) = reload_cmd {
-        match Command::new(&cmd).args(&args).output() {
+        match Command::new(cmd).args(args).output() {
             Ok(_) => println!("  ↻ Reloaded {}", name),
             Err(_) => println!("  ⚠ Could not reload {} (not running?)", name),
         }
     }
-    
+}
+
+pub fn try_theme(theme_name: &str, home: &str, duration: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let timeout = duration.map(parse_duration).transpose()?;
+    let backups = apply_theme_with_backup(theme_name, home, false, false)?;
+
+    // Ctrl-C or a SIGTERM/SIGHUP reverts too, instead of leaving the theme applied
+    let (tx, rx) = mpsc::channel();
+    let signal_tx = tx.clone();
+    if let Err(e) = ctrlc::set_handler(move || { signal_tx.send(()).ok(); }) {
+        restore_backups(&backups)?;
+        return Err(e.into());
+    }
+
+    match duration {
+        Some(d) => println!("\nTrying theme for {}. Press Enter or Ctrl-C to revert early.", d),
+        None => println!("\nTrying theme. Press Enter or Ctrl-C to revert."),
+    }
+
+    // Wait on stdin in the background so the timeout can win the race
+    thread::spawn(move || {
+        let mut line = String::new();
+        // EOF means there's no terminal to answer from, so keep waiting
+        if let Ok(n) = io::stdin().read_line(&mut line)
+            && n > 0
+        {
+            tx.send(()).ok();
+        }
+    });
+
+    match timeout {
+        Some(t) => { rx.recv_timeout(t).ok(); },
+        None => { rx.recv().ok(); },
+    }
+
+    // Put the previous files back exactly as they were
+    restore_backups(&backups)?;
+
+    println!("Reverted theme '{}'", theme_name);
     Ok(())
 }
 
-pub fn preview_theme_rgb(theme_name: &str, home: &str) -> Result<(), Box<dyn std::error::Error>> {
+pub fn preview_theme_rgb(theme_name: &str, home: &str, sample: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
     let base_path = format!("{}/.config/themey/themes/{}", home, theme_name);
     let metadata_path = format!("{}/metadata.toml", base_path);
     let metadata = parse_metadata(&metadata_path)?;
@@ -68,6 +258,11 @@ pub fn preview_theme_rgb(theme_name: &str, home: &str) -> Result<(), Box<dyn std
 
     draw_color_panes_rgb(&colors)?;
 
+    if let Some(sample) = sample {
+        println!();
+        draw_sample_rgb(&colors, sample)?;
+    }
+
     println!();
     Ok(())
 }
@@ -107,7 +302,7 @@ fn draw_color_panes_rgb(colors: &Colors) -> Result<(), Box<dyn std::error::Error
             write!(handle, "\x1b[48;2;{};{};{}m", r, g, b)?;
             write!(handle, "{}", " ".repeat(pane_width))?;
         }
-        write!(handle, "\x1b[0m\n")?;
+        writeln!(handle, "\x1b[0m")?;
     }
     
     for _ in 0..height {
@@ -116,17 +311,184 @@ fn draw_color_panes_rgb(colors: &Colors) -> Result<(), Box<dyn std::error::Error
             write!(handle, "\x1b[48;2;{};{};{}m", r, g, b)?;
             write!(handle, "{}", " ".repeat(pane_width))?;
         }
-        write!(handle, "\x1b[0m\n")?;
+        writeln!(handle, "\x1b[0m")?;
     }
     
     handle.flush()?;
     Ok(())
 }
 
-fn hex_to_rgb_tuple(hex: &str) -> (u8, u8, u8) {
+pub fn hex_to_rgb_tuple(hex: &str) -> (u8, u8, u8) {
     let hex = hex.trim_start_matches('#');
-    let r = u8::from_str_radix(&hex[0..2], 16).unwrap_or(0);
-    let g = u8::from_str_radix(&hex[2..4], 16).unwrap_or(0);
-    let b = u8::from_str_radix(&hex[4..6], 16).unwrap_or(0);
-    (r, g, b)
+    // Malformed input reads as black rather than panicking on a bad slice
+    let channel = |range| hex.get(range).and_then(|c| u8::from_str_radix(c, 16).ok()).unwrap_or(0);
+    (channel(0..2), channel(2..4), channel(4..6))
+}
+
+// A line of sample content, as (color, text) segments
+type SampleLine<'a> = Vec<(&'a str, &'a str)>;
+
+fn draw_sample_rgb(colors: &Colors, sample: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let lines = match sample {
+        "code" => sample_code(colors),
+        "shell" => sample_shell(colors),
+        "diff" => sample_diff(colors),
+        "ls" => sample_ls(colors),
+        "log" => sample_log(colors),
+        _ => return Err(format!("Unknown sample: {}", sample).into()),
+    };
+
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+
+    // Match the width of the color panes above
+    let width = 64;
+    let (br, bg, bb) = hex_to_rgb_tuple(&colors.special.background);
+
+    for line in lines {
+        write!(handle, "\x1b[48;2;{};{};{}m  ", br, bg, bb)?;
+        let mut len = 2;
+        for (color, text) in line {
+            let (r, g, b) = hex_to_rgb_tuple(color);
+            write!(handle, "\x1b[38;2;{};{};{}m{}", r, g, b, text)?;
+            len += text.chars().count();
+        }
+        write!(handle, "{}", " ".repeat(width - len.min(width)))?;
+        writeln!(handle, "\x1b[0m")?;
+    }
+
+    handle.flush()?;
+    Ok(())
+}
+
+fn sample_code(colors: &Colors) -> Vec<SampleLine<'_>> {
+    let fg = colors.special.foreground.as_str();
+    let comment = colors.bright.black.as_str();
+    let keyword = colors.normal.red.as_str();
+    let function = colors.normal.blue.as_str();
+    let string = colors.normal.green.as_str();
+    let number = colors.normal.magenta.as_str();
+    let ty = colors.normal.yellow.as_str();
+    let ident = colors.normal.cyan.as_str();
+
+    vec![
+        vec![],
+        vec![(comment, "// Greet everyone on the list")],
+        vec![(keyword, "fn "), (function, "greet"), (fg, "("), (ident, "names"), (fg, ": &["), (ty, "String"), (fg, "]) -> "), (ty, "usize"), (fg, " {")],
+        vec![(keyword, "    let "), (fg, "mut "), (ident, "count"), (fg, " = "), (number, "0"), (fg, ";")],
+        vec![(keyword, "    for "), (ident, "name"), (keyword, " in "), (ident, "names"), (fg, " {")],
+        vec![(function, "        println!"), (fg, "("), (string, "\"Hello, {}!\""), (fg, ", "), (ident, "name"), (fg, ");")],
+        vec![(ident, "        count"), (fg, " += "), (number, "1"), (fg, ";")],
+        vec![(fg, "    }")],
+        vec![(ident, "    count")],
+        vec![(fg, "}")],
+        vec![],
+    ]
+}
+
+fn sample_shell(colors: &Colors) -> Vec<SampleLine<'_>> {
+    let fg = colors.special.foreground.as_str();
+    let cursor = colors.special.cursor.as_str();
+    let user = colors.normal.green.as_str();
+    let path = colors.normal.blue.as_str();
+    let branch = colors.normal.magenta.as_str();
+    let muted = colors.bright.black.as_str();
+    let error = colors.normal.red.as_str();
+
+    vec![
+        vec![],
+        vec![(user, "user@host"), (fg, " "), (path, "~/projects/themey"), (fg, " on "), (branch, "main")],
+        vec![(fg, "$ cargo build --release")],
+        vec![(user, "   Compiling"), (fg, " themey v0.1.0")],
+        vec![(user, "    Finished"), (fg, " `release` profile "), (muted, "[optimized]")],
+        vec![(user, "user@host"), (fg, " "), (path, "~/projects/themey"), (fg, " on "), (branch, "main")],
+        vec![(fg, "$ themey use missing-theme")],
+        vec![(error, "Failed to apply theme: No such file or directory")],
+        vec![(user, "user@host"), (fg, " "), (path, "~/projects/themey"), (fg, " on "), (branch, "main")],
+        vec![(fg, "$ "), (cursor, "█")],
+        vec![],
+    ]
+}
+
+fn sample_diff(colors: &Colors) -> Vec<SampleLine<'_>> {
+    let fg = colors.special.foreground.as_str();
+    let meta = colors.bright.white.as_str();
+    let hunk = colors.normal.cyan.as_str();
+    let added = colors.normal.green.as_str();
+    let removed = colors.normal.red.as_str();
+    let commit = colors.normal.yellow.as_str();
+
+    vec![
+        vec![],
+        vec![(commit, "commit 3f2a9c1 "), (fg, "(HEAD -> main)")],
+        vec![(meta, "diff --git a/src/main.rs b/src/main.rs")],
+        vec![(meta, "--- a/src/main.rs")],
+        vec![(meta, "+++ b/src/main.rs")],
+        vec![(hunk, "@@ -12,7 +12,8 @@"), (fg, " fn main() {")],
+        vec![(fg, "     let cli = Cli::parse();")],
+        vec![(removed, "-    let home = env::var(\"HOME\").unwrap();")],
+        vec![(added, "+    let home = env::var(\"HOME\")")],
+        vec![(added, "+        .expect(\"HOME is not set\");")],
+        vec![(fg, "     utils::config_folder().ok();")],
+        vec![],
+    ]
+}
+
+fn sample_ls(colors: &Colors) -> Vec<SampleLine<'_>> {
+    let fg = colors.special.foreground.as_str();
+    let dir = colors.normal.blue.as_str();
+    let link = colors.normal.cyan.as_str();
+    let exec = colors.normal.green.as_str();
+    let archive = colors.normal.red.as_str();
+    let media = colors.normal.magenta.as_str();
+    let muted = colors.bright.black.as_str();
+
+    vec![
+        vec![],
+        vec![(fg, "$ ls -l --color")],
+        vec![(muted, "drwxr-xr-x  "), (dir, "Documents")],
+        vec![(muted, "drwxr-xr-x  "), (dir, "projects")],
+        vec![(muted, "lrwxrwxrwx  "), (link, "config"), (fg, " -> "), (dir, ".config")],
+        vec![(muted, "-rwxr-xr-x  "), (exec, "build.sh")],
+        vec![(muted, "-rw-r--r--  "), (archive, "backup.tar.gz")],
+        vec![(muted, "-rw-r--r--  "), (media, "wallpaper.png")],
+        vec![(muted, "-rw-r--r--  "), (fg, "notes.md")],
+        vec![],
+    ]
+}
+
+fn sample_log(colors: &Colors) -> Vec<SampleLine<'_>> {
+    let fg = colors.special.foreground.as_str();
+    let time = colors.bright.black.as_str();
+    let trace = colors.bright.black.as_str();
+    let debug = colors.normal.blue.as_str();
+    let info = colors.normal.green.as_str();
+    let warn = colors.normal.yellow.as_str();
+    let error = colors.normal.red.as_str();
+
+    vec![
+        vec![],
+        vec![(time, "12:00:01 "), (trace, "TRACE "), (fg, "polling theme directory")],
+        vec![(time, "12:00:01 "), (debug, "DEBUG "), (fg, "parsed metadata.toml")],
+        vec![(time, "12:00:02 "), (info, "INFO  "), (fg, "applied theme 'neosleek'")],
+        vec![(time, "12:00:02 "), (warn, "WARN  "), (fg, "could not reload waybar")],
+        vec![(time, "12:00:03 "), (error, "ERROR "), (fg, "failed to write colors.conf")],
+        vec![],
+    ]
+}
+
+// WCAG contrast ratio between two colors, from 1.0 to 21.0
+pub fn contrast_ratio(a: &str, b: &str) -> f64 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    let (hi, lo) = if la > lb { (la, lb) } else { (lb, la) };
+    (hi + 0.05) / (lo + 0.05)
+}
+
+pub fn relative_luminance(hex: &str) -> f64 {
+    let (r, g, b) = hex_to_rgb_tuple(hex);
+    let channel = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.03928 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    };
+    0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
 }
@@ -0,0 +1,348 @@
+use super::colors::hex_to_rgb_tuple;
+use super::parser::{parse_metadata, parse_colors, Colors};
+use super::utils::escape_xml;
+use std::fs;
+
+type Rgb = (u8, u8, u8);
+
+// Drawing operations shared by the SVG and PNG backends
+enum Op {
+    Rect { x: u32, y: u32, w: u32, h: u32, color: Rgb },
+    Text { x: u32, y: u32, scale: u32, color: Rgb, text: String },
+}
+
+// Everything the card shows besides the colors themselves
+pub struct CardInfo<'a> {
+    pub name: &'a str,
+    pub author: &'a str,
+    pub description: &'a str,
+    pub variants: &'a [String],
+    pub active: usize,
+}
+
+const PAD: u32 = 24;
+const SWATCH_W: u32 = 88;
+const SWATCH_H: u32 = 56;
+const GAP: u32 = 8;
+const WIDTH: u32 = PAD * 2 + SWATCH_W * 8 + GAP * 7;
+
+// Glyph cell of the bitmap font, before scaling
+const CHAR_W: u32 = 6;
+const CHAR_H: u32 = 8;
+
+pub fn export_preview(
+    theme_name: &str,
+    home: &str,
+    format: &str,
+    output: Option<&str>,
+    variant: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let base_path = format!("{}/.config/themey/themes/{}", home, theme_name);
+    let metadata_path = format!("{}/metadata.toml", base_path);
+    let metadata = parse_metadata(&metadata_path)?;
+
+    let active = match variant {
+        Some(v) => metadata.variants.iter().position(|x| x == v)
+            .ok_or_else(|| format!("Unknown variant: {}", v))?,
+        None => 0,
+    };
+
+    let theme_file = metadata.files.get(active)
+        .ok_or_else(|| format!("No file for variant #{}", active))?;
+    let theme_path = format!("{}/{}", base_path, theme_file);
+    let colors = parse_colors(&theme_path)?;
+
+    let info = CardInfo {
+        name: &metadata.name,
+        author: &metadata.author,
+        description: &metadata.description,
+        variants: &metadata.variants,
+        active,
+    };
+
+    let default_output = format!("card.{}", format);
+    let output = output.unwrap_or(&default_output);
+
+    fs::write(output, render_card(&info, &colors, format)?)?;
+    println!("✓ Exported: {}", output);
+
+    Ok(())
+}
+
+pub fn render_card(info: &CardInfo, colors: &Colors, format: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let (height, ops) = layout(info, colors);
+    let bg = hex_to_rgb_tuple(&colors.special.background);
+
+    match format {
+        "svg" => Ok(to_svg(height, bg, &ops).into_bytes()),
+        "png" => to_png(height, bg, &ops),
+        _ => Err(format!("Unknown format: {}", format).into()),
+    }
+}
+
+fn layout(info: &CardInfo, colors: &Colors) -> (u32, Vec<Op>) {
+    let fg = hex_to_rgb_tuple(&colors.special.foreground);
+    let bg = hex_to_rgb_tuple(&colors.special.background);
+    let muted = hex_to_rgb_tuple(&colors.bright.black);
+    let accent = hex_to_rgb_tuple(&colors.normal.blue);
+
+    let mut ops = Vec::new();
+    let mut y = PAD;
+
+    // Header, kept inside the card: the name is cut short, the description wraps
+    ops.push(Op::Text { x: PAD, y, scale: 3, color: fg, text: ellipsize(info.name, columns(3)) });
+    y += CHAR_H * 3 + 12;
+    ops.push(Op::Text { x: PAD, y, scale: 2, color: muted, text: ellipsize(&format!("by {}", info.author), columns(2)) });
+    y += CHAR_H * 2 + 8;
+    for line in wrap(info.description, columns(2)) {
+        ops.push(Op::Text { x: PAD, y, scale: 2, color: fg, text: line });
+        y += CHAR_H * 2 + 8;
+    }
+    y += 8;
+
+    // Variant tabs, starting a new row when one would run past the edge
+    if !info.variants.is_empty() {
+        let mut x = PAD;
+        for (i, v) in info.variants.iter().enumerate() {
+            let label = ellipsize(v, columns(2) - 2);
+            let w = label.chars().count() as u32 * CHAR_W * 2 + 24;
+            if x > PAD && x + w > WIDTH - PAD {
+                x = PAD;
+                y += 28 + GAP;
+            }
+            let (fill, text) = if i == info.active { (accent, bg) } else { (muted, fg) };
+            ops.push(Op::Rect { x, y, w, h: 28, color: fill });
+            ops.push(Op::Text { x: x + 12, y: y + 7, scale: 2, color: text, text: label });
+            x += w + GAP;
+        }
+        y += 28 + 20;
+    }
+
+    // Palette swatches with hex labels
+    let rows = [
+        [
+            &colors.normal.black, &colors.normal.red, &colors.normal.green, &colors.normal.yellow,
+            &colors.normal.blue, &colors.normal.magenta, &colors.normal.cyan, &colors.normal.white,
+        ],
+        [
+            &colors.bright.black, &colors.bright.red, &colors.bright.green, &colors.bright.yellow,
+            &colors.bright.blue, &colors.bright.magenta, &colors.bright.cyan, &colors.bright.white,
+        ],
+    ];
+    for row in rows {
+        for (i, hex) in row.iter().enumerate() {
+            let x = PAD + i as u32 * (SWATCH_W + GAP);
+            swatch(&mut ops, x, y, hex, fg, muted);
+        }
+        y += SWATCH_H + 8 + CHAR_H * 2 + 16;
+    }
+
+    // Special colors
+    let special = [
+        ("background", &colors.special.background),
+        ("foreground", &colors.special.foreground),
+        ("cursor", &colors.special.cursor),
+    ];
+    for (i, (label, hex)) in special.iter().enumerate() {
+        let x = PAD + i as u32 * (SWATCH_W + GAP * 4 + CHAR_W * 2 * 10);
+        swatch(&mut ops, x, y, hex, fg, muted);
+        ops.push(Op::Text { x: x + SWATCH_W + GAP, y: y + 20, scale: 2, color: muted, text: label.to_string() });
+    }
+    y += SWATCH_H + 8 + CHAR_H * 2 + 24;
+
+    // Sample text
+    let keyword = hex_to_rgb_tuple(&colors.normal.red);
+    let function = hex_to_rgb_tuple(&colors.normal.blue);
+    let string = hex_to_rgb_tuple(&colors.normal.green);
+    let number = hex_to_rgb_tuple(&colors.normal.magenta);
+    let lines: [&[(Rgb, &str)]; 3] = [
+        &[(muted, "// The quick brown fox jumps over the lazy dog")],
+        &[(keyword, "let "), (fg, "answer = "), (function, "compute"), (fg, "("), (number, "42"), (fg, ");")],
+        &[(function, "println!"), (fg, "("), (string, "\"{}\""), (fg, ", answer);")],
+    ];
+    for line in lines {
+        let mut x = PAD;
+        for (color, text) in line {
+            ops.push(Op::Text { x, y, scale: 2, color: *color, text: text.to_string() });
+            x += text.chars().count() as u32 * CHAR_W * 2;
+        }
+        y += CHAR_H * 2 + 8;
+    }
+
+    (y + PAD - 8, ops)
+}
+
+// How many characters fit across the card at a text scale
+fn columns(scale: u32) -> usize {
+    ((WIDTH - PAD * 2) / (CHAR_W * scale)) as usize
+}
+
+// Cut text to `max` characters, marking the cut with "..."
+fn ellipsize(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max.saturating_sub(3)).collect();
+    format!("{}...", kept.trim_end())
+}
+
+// Break text into lines of at most `max` characters, at spaces where possible
+fn wrap(text: &str, max: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        // Words longer than a whole line are split across lines
+        while word.len() > max {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            lines.push(word.drain(..max).collect());
+        }
+        if word.is_empty() {
+            continue;
+        }
+        if !line.is_empty() && line.chars().count() + 1 + word.len() > max {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.extend(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+fn swatch(ops: &mut Vec<Op>, x: u32, y: u32, hex: &str, fg: Rgb, outline: Rgb) {
+    // Outline keeps swatches that match the background visible
+    ops.push(Op::Rect { x, y, w: SWATCH_W, h: SWATCH_H, color: outline });
+    ops.push(Op::Rect { x: x + 1, y: y + 1, w: SWATCH_W - 2, h: SWATCH_H - 2, color: hex_to_rgb_tuple(hex) });
+    ops.push(Op::Text { x, y: y + SWATCH_H + 8, scale: 2, color: fg, text: hex.to_string() });
+}
+
+fn to_svg(height: u32, bg: Rgb, ops: &[Op]) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+        w = WIDTH,
+        h = height,
+    );
+    svg.push_str(&format!("  <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n", css(bg)));
+
+    for op in ops {
+        match op {
+            Op::Rect { x, y, w, h, color } => {
+                svg.push_str(&format!(
+                    "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+                    x, y, w, h, css(*color),
+                ));
+            }
+            Op::Text { x, y, scale, color, text } => {
+                // Monospace advance is ~0.6em, which matches the bitmap cell width
+                svg.push_str(&format!(
+                    "  <text x=\"{}\" y=\"{}\" font-family=\"monospace\" font-size=\"{}\" fill=\"{}\" xml:space=\"preserve\">{}</text>\n",
+                    x, y + 7 * scale, 10 * scale, css(*color), escape_xml(text),
+                ));
+            }
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn to_png(height: u32, bg: Rgb, ops: &[Op]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut pixels = vec![0u8; (WIDTH * height * 3) as usize];
+    fill(&mut pixels, 0, 0, WIDTH, height, height, bg);
+
+    for op in ops {
+        match op {
+            Op::Rect { x, y, w, h, color } => fill(&mut pixels, *x, *y, *w, *h, height, *color),
+            Op::Text { x, y, scale, color, text } => {
+                for (i, c) in text.chars().enumerate() {
+                    let cx = x + i as u32 * CHAR_W * scale;
+                    for (col, bits) in glyph(c).iter().enumerate() {
+                        for row in 0..CHAR_H {
+                            if bits >> row & 1 == 1 {
+                                let px = cx + col as u32 * scale;
+                                let py = y + row * scale;
+                                fill(&mut pixels, px, py, *scale, *scale, height, *color);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, WIDTH, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&pixels)?;
+    }
+    Ok(out)
+}
+
+fn fill(pixels: &mut [u8], x: u32, y: u32, w: u32, h: u32, height: u32, color: Rgb) {
+    for py in y..(y + h).min(height) {
+        for px in x..(x + w).min(WIDTH) {
+            let i = ((py * WIDTH + px) * 3) as usize;
+            pixels[i] = color.0;
+            pixels[i + 1] = color.1;
+            pixels[i + 2] = color.2;
+        }
+    }
+}
+
+fn css(c: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", c.0, c.1, c.2)
+}
+
+// Classic 5x7 font for printable ASCII, one byte per column with the top row in bit 0.
+// Lowercase letters with descenders use the eighth row.
+fn glyph(c: char) -> [u8; 5] {
+    const FONT: [[u8; 5]; 95] = [
+        [0x00, 0x00, 0x00, 0x00, 0x00], [0x00, 0x00, 0x5F, 0x00, 0x00], [0x00, 0x07, 0x00, 0x07, 0x00],
+        [0x14, 0x7F, 0x14, 0x7F, 0x14], [0x24, 0x2A, 0x7F, 0x2A, 0x12], [0x23, 0x13, 0x08, 0x64, 0x62],
+        [0x36, 0x49, 0x55, 0x22, 0x50], [0x00, 0x05, 0x03, 0x00, 0x00], [0x00, 0x1C, 0x22, 0x41, 0x00],
+        [0x00, 0x41, 0x22, 0x1C, 0x00], [0x08, 0x2A, 0x1C, 0x2A, 0x08], [0x08, 0x08, 0x3E, 0x08, 0x08],
+        [0x00, 0x50, 0x30, 0x00, 0x00], [0x08, 0x08, 0x08, 0x08, 0x08], [0x00, 0x60, 0x60, 0x00, 0x00],
+        [0x20, 0x10, 0x08, 0x04, 0x02], [0x3E, 0x51, 0x49, 0x45, 0x3E], [0x00, 0x42, 0x7F, 0x40, 0x00],
+        [0x42, 0x61, 0x51, 0x49, 0x46], [0x21, 0x41, 0x45, 0x4B, 0x31], [0x18, 0x14, 0x12, 0x7F, 0x10],
+        [0x27, 0x45, 0x45, 0x45, 0x39], [0x3C, 0x4A, 0x49, 0x49, 0x30], [0x01, 0x71, 0x09, 0x05, 0x03],
+        [0x36, 0x49, 0x49, 0x49, 0x36], [0x06, 0x49, 0x49, 0x29, 0x1E], [0x00, 0x36, 0x36, 0x00, 0x00],
+        [0x00, 0x56, 0x36, 0x00, 0x00], [0x08, 0x14, 0x22, 0x41, 0x00], [0x14, 0x14, 0x14, 0x14, 0x14],
+        [0x00, 0x41, 0x22, 0x14, 0x08], [0x02, 0x01, 0x51, 0x09, 0x06], [0x32, 0x49, 0x79, 0x41, 0x3E],
+        [0x7E, 0x11, 0x11, 0x11, 0x7E], [0x7F, 0x49, 0x49, 0x49, 0x36], [0x3E, 0x41, 0x41, 0x41, 0x22],
+        [0x7F, 0x41, 0x41, 0x22, 0x1C], [0x7F, 0x49, 0x49, 0x49, 0x41], [0x7F, 0x09, 0x09, 0x09, 0x01],
+        [0x3E, 0x41, 0x49, 0x49, 0x7A], [0x7F, 0x08, 0x08, 0x08, 0x7F], [0x00, 0x41, 0x7F, 0x41, 0x00],
+        [0x20, 0x40, 0x41, 0x3F, 0x01], [0x7F, 0x08, 0x14, 0x22, 0x41], [0x7F, 0x40, 0x40, 0x40, 0x40],
+        [0x7F, 0x02, 0x0C, 0x02, 0x7F], [0x7F, 0x04, 0x08, 0x10, 0x7F], [0x3E, 0x41, 0x41, 0x41, 0x3E],
+        [0x7F, 0x09, 0x09, 0x09, 0x06], [0x3E, 0x41, 0x51, 0x21, 0x5E], [0x7F, 0x09, 0x19, 0x29, 0x46],
+        [0x46, 0x49, 0x49, 0x49, 0x31], [0x01, 0x01, 0x7F, 0x01, 0x01], [0x3F, 0x40, 0x40, 0x40, 0x3F],
+        [0x1F, 0x20, 0x40, 0x20, 0x1F], [0x3F, 0x40, 0x38, 0x40, 0x3F], [0x63, 0x14, 0x08, 0x14, 0x63],
+        [0x07, 0x08, 0x70, 0x08, 0x07], [0x61, 0x51, 0x49, 0x45, 0x43], [0x00, 0x7F, 0x41, 0x41, 0x00],
+        [0x02, 0x04, 0x08, 0x10, 0x20], [0x00, 0x41, 0x41, 0x7F, 0x00], [0x04, 0x02, 0x01, 0x02, 0x04],
+        [0x40, 0x40, 0x40, 0x40, 0x40], [0x00, 0x01, 0x02, 0x04, 0x00], [0x20, 0x54, 0x54, 0x54, 0x78],
+        [0x7F, 0x48, 0x44, 0x44, 0x38], [0x38, 0x44, 0x44, 0x44, 0x20], [0x38, 0x44, 0x44, 0x48, 0x7F],
+        [0x38, 0x54, 0x54, 0x54, 0x18], [0x08, 0x7E, 0x09, 0x01, 0x02], [0x18, 0xA4, 0xA4, 0xA4, 0x7C],
+        [0x7F, 0x08, 0x04, 0x04, 0x78], [0x00, 0x44, 0x7D, 0x40, 0x00], [0x40, 0x80, 0x80, 0x84, 0x7D],
+        [0x7F, 0x10, 0x28, 0x44, 0x00], [0x00, 0x41, 0x7F, 0x40, 0x00], [0x7C, 0x04, 0x18, 0x04, 0x78],
+        [0x7C, 0x08, 0x04, 0x04, 0x78], [0x38, 0x44, 0x44, 0x44, 0x38], [0xFC, 0x24, 0x24, 0x24, 0x18],
+        [0x18, 0x24, 0x24, 0x24, 0xFC], [0x7C, 0x08, 0x04, 0x04, 0x08], [0x48, 0x54, 0x54, 0x54, 0x20],
+        [0x04, 0x3F, 0x44, 0x40, 0x20], [0x3C, 0x40, 0x40, 0x20, 0x7C], [0x1C, 0x20, 0x40, 0x20, 0x1C],
+        [0x3C, 0x40, 0x30, 0x40, 0x3C], [0x44, 0x28, 0x10, 0x28, 0x44], [0x1C, 0xA0, 0xA0, 0xA0, 0x7C],
+        [0x44, 0x64, 0x54, 0x4C, 0x44], [0x00, 0x08, 0x36, 0x41, 0x00], [0x00, 0x00, 0x7F, 0x00, 0x00],
+        [0x00, 0x41, 0x36, 0x08, 0x00], [0x08, 0x04, 0x08, 0x10, 0x08],
+    ];
+
+    match c {
+        ' '..='~' => FONT[c as usize - 32],
+        _ => FONT['?' as usize - 32],
+    }
+}
@@ -0,0 +1,174 @@
+use super::colors::contrast_ratio;
+use super::parser::{parse_metadata, parse_colors, Colors};
+use super::utils::{escape_xml, list_themes};
+use git2::Repository;
+use std::fs;
+use std::path::Path;
+
+pub fn build_gallery(home: &str, out_dir: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let themes_path = format!("{}/.config/themey/themes", home);
+    let mut names = list_themes(&themes_path);
+    names.sort();
+
+    let mut cards = String::new();
+    for name in &names {
+        match theme_card(&themes_path, name) {
+            Ok(card) => cards.push_str(&card),
+            Err(e) => eprintln!("Skipping '{}': {}", name, e),
+        }
+    }
+
+    let html = format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>Themey gallery</title>
+<style>
+body {{ font-family: sans-serif; background: #f4f4f5; color: #18181b; margin: 2rem auto; max-width: 960px; }}
+.theme {{ background: #fff; border-radius: 8px; padding: 1rem 1.5rem; margin-bottom: 1.5rem; box-shadow: 0 1px 3px rgba(0, 0, 0, 0.15); }}
+.theme h2 {{ margin: 0; }}
+.author {{ color: #71717a; margin: 0.25rem 0; }}
+.variant {{ border-radius: 6px; padding: 0.75rem; margin-top: 0.75rem; }}
+.variant h3 {{ margin: 0 0 0.5rem 0; font-size: 1rem; }}
+.swatches {{ display: flex; flex-wrap: wrap; gap: 4px; }}
+.swatch {{ width: 48px; height: 32px; border-radius: 4px; border: 1px solid rgba(127, 127, 127, 0.4); }}
+.contrast {{ font-family: monospace; margin-top: 0.5rem; }}
+.pull {{ display: flex; gap: 0.5rem; margin-top: 0.75rem; }}
+.pull code {{ background: #f4f4f5; padding: 0.25rem 0.5rem; border-radius: 4px; }}
+</style>
+</head>
+<body>
+<h1>Themey gallery</h1>
+<p>{count} installed theme(s)</p>
+{cards}<script>
+function copy(button) {{
+  navigator.clipboard.writeText(button.dataset.cmd);
+  button.textContent = 'Copied!';
+  setTimeout(() => button.textContent = 'Copy', 1500);
+}}
+</script>
+</body>
+</html>
+",
+        count = names.len(),
+        cards = cards,
+    );
+
+    fs::create_dir_all(out_dir)?;
+    let index_path = Path::new(out_dir).join("index.html");
+    fs::write(&index_path, html)?;
+
+    println!("✓ Created: {}", index_path.display());
+    Ok(())
+}
+
+fn theme_card(themes_path: &str, name: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let base_path = format!("{}/{}", themes_path, name);
+    let metadata = parse_metadata(&format!("{}/metadata.toml", base_path))?;
+
+    let mut variants = String::new();
+    for (i, file) in metadata.files.iter().enumerate() {
+        let variant = metadata.variants.get(i).cloned()
+            .unwrap_or_else(|| file.replace(".toml", ""));
+        let colors = parse_colors(&format!("{}/{}", base_path, file))?;
+        variants.push_str(&variant_block(&variant, &colors));
+    }
+
+    // Themes pulled from GitHub can be shared with a pull command
+    let pull = match github_slug(&base_path) {
+        Some(slug) => {
+            let cmd = format!("themey pull {}", slug);
+            format!(
+                "<div class=\"pull\"><code>{cmd}</code><button data-cmd=\"{cmd}\" onclick=\"copy(this)\">Copy</button></div>\n",
+                cmd = escape_xml(&cmd),
+            )
+        }
+        None => String::new(),
+    };
+
+    Ok(format!(
+        "<section class=\"theme\">
+<h2>{name}</h2>
+<p class=\"author\">by {author}</p>
+<p>{description}</p>
+{variants}{pull}</section>
+",
+        name = escape_xml(&metadata.name),
+        author = escape_xml(&metadata.author),
+        description = escape_xml(&metadata.description),
+        variants = variants,
+        pull = pull,
+    ))
+}
+
+fn variant_block(variant: &str, colors: &Colors) -> String {
+    let palette = [
+        &colors.normal.black,
+        &colors.normal.red,
+        &colors.normal.green,
+        &colors.normal.yellow,
+        &colors.normal.blue,
+        &colors.normal.magenta,
+        &colors.normal.cyan,
+        &colors.normal.white,
+        &colors.bright.black,
+        &colors.bright.red,
+        &colors.bright.green,
+        &colors.bright.yellow,
+        &colors.bright.blue,
+        &colors.bright.magenta,
+        &colors.bright.cyan,
+        &colors.bright.white,
+    ];
+
+    let swatches: String = palette.iter()
+        .map(|c| format!(
+            "<div class=\"swatch\" style=\"background: {c}\" title=\"{c}\"></div>",
+            c = escape_xml(c),
+        ))
+        .collect();
+
+    // Text contrast and the weakest palette color against the background
+    let text_contrast = contrast_ratio(&colors.special.foreground, &colors.special.background);
+    let min_contrast = palette.iter()
+        .map(|c| contrast_ratio(c, &colors.special.background))
+        .fold(f64::MAX, f64::min);
+
+    format!(
+        "<div class=\"variant\" style=\"background: {bg}; color: {fg}\">
+<h3>{variant}</h3>
+<div class=\"swatches\">{swatches}</div>
+<div class=\"contrast\">text {text:.2}:1 ({rating}) &middot; lowest palette {min:.2}:1</div>
+</div>
+",
+        bg = escape_xml(&colors.special.background),
+        fg = escape_xml(&colors.special.foreground),
+        variant = escape_xml(variant),
+        swatches = swatches,
+        text = text_contrast,
+        rating = wcag_rating(text_contrast),
+        min = min_contrast,
+    )
+}
+
+fn wcag_rating(ratio: f64) -> &'static str {
+    if ratio >= 7.0 {
+        "AAA"
+    } else if ratio >= 4.5 {
+        "AA"
+    } else if ratio >= 3.0 {
+        "AA large"
+    } else {
+        "fail"
+    }
+}
+
+// "user/repo" for themes cloned from GitHub
+fn github_slug(path: &str) -> Option<String> {
+    let repo = Repository::open(path).ok()?;
+    let remote = repo.find_remote("origin").ok()?;
+    let url = remote.url()?;
+    let slug = url.strip_prefix("https://github.com/")?;
+    Some(slug.trim_end_matches(".git").trim_end_matches('/').to_string())
+}
@@ -1,4 +1,4 @@
-use dialoguer::{Input, MultiSelect, theme::ColorfulTheme};
+use dialoguer::{Confirm, Input, MultiSelect, theme::ColorfulTheme};
 use serde::Serialize;
 use std::fs::{create_dir_all, write};
 use std::path::Path;
@@ -9,6 +9,52 @@ use super::parser::{
     parse_metadata,
     parse_colors,
 };
+use super::export::{CardInfo, render_card};
+
+// Targets offered by the generate/update pickers
+const TARGETS: &[&str] = &[
+    "kitty",
+    "waybar",
+    "hyprland",
+    "rofi",
+    "dunst",
+    "foot",
+    "gtk",
+    "neovim",
+    "alacritty",
+    "wezterm",
+    "ghostty",
+    "tmux",
+    "sway",
+    "i3",
+    "xresources",
+    "mako",
+    "swaync",
+    "polybar",
+    "eww",
+    "helix",
+    "vim",
+    "vscode",
+    "emacs",
+    "bat",
+    "dircolors",
+    "fzf",
+    "starship",
+    "fish",
+    "btop",
+    "lazygit",
+    "yazi",
+    "zathura",
+    "qt",
+    "firefox",
+    "vesktop",
+    "obsidian",
+    "spicetify",
+    "hyprlock",
+    "swaylock",
+    "fuzzel",
+    "wofi",
+];
 
 #[derive(Serialize)]
 struct Theme {
@@ -35,8 +81,6 @@ struct ColorFile {
 }
 
 fn capture_metadata(default_name: Option<&str>) -> (Theme, String) {
-    let targets = &["kitty", "waybar", "hyprland", "rofi", "dunst", "foot", "gtk", "neovim"];
-
     let name: String = if let Some(default) = default_name {
         Input::with_theme(&ColorfulTheme::default())
             .with_prompt("Theme name:")
@@ -102,12 +146,12 @@ fn capture_metadata(default_name: Option<&str>) -> (Theme, String) {
         .collect();
 
     let idx = MultiSelect::new()
-        .items(targets)
+        .items(TARGETS)
         .interact()
         .unwrap();
 
     let sel: Vec<String> = idx.into_iter()
-        .map(|i| targets[i].to_string())
+        .map(|i| TARGETS[i].to_string())
         .collect();
 
     let theme_folder = name.to_lowercase().replace(" ", "-");
@@ -476,6 +520,28 @@ pub fn create_theme_package(theme_name: Option<&str>) -> Result<(), Box<dyn std:
     write(&metadata_path, metadata_str)?;
 
     println!("\n✓ Created: {}", metadata_path.display());
+
+    // Optionally add a palette card for the theme's README
+    let want_card = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Create a palette card (preview.svg)?")
+        .default(true)
+        .interact()
+        .unwrap();
+
+    if want_card && let Some(first) = color_files.first() {
+        let info = CardInfo {
+            name: &root.theme.name,
+            author: &root.theme.author,
+            description: &root.theme.description,
+            variants: &root.theme.variants,
+            active: 0,
+        };
+        let card_path = theme_path.join("preview.svg");
+        write(&card_path, render_card(&info, &first.colors, "svg")?)?;
+
+        println!("\n✓ Created: {}", card_path.display());
+    }
+
     println!("\n-> Theme package created successfully in '{}'", theme_folder);
 
     Ok(())
@@ -541,7 +607,7 @@ pub fn update_theme_package(theme_name: &str, home: &str) -> Result<(), Box<dyn
             .unwrap();
 
         // For optional fields, we need to handle None
-        let homepage_default = existing_metadata.files.get(0).map(|_| "").unwrap_or("");
+        let homepage_default = existing_metadata.files.first().map(|_| "").unwrap_or("");
         let homepage_input: String = Input::with_theme(&ColorfulTheme::default())
             .with_prompt("(optional) homepage:")
             .with_initial_text(homepage_default)
@@ -582,20 +648,19 @@ pub fn update_theme_package(theme_name: &str, home: &str) -> Result<(), Box<dyn
     // Update targets if selected
     if update_targets {
         println!("\n-> Updating Targets\n");
-        let all_targets = &["kitty", "waybar", "hyprland", "rofi", "dunst", "foot", "gtk"];
-        let defaults: Vec<bool> = all_targets.iter()
+        let defaults: Vec<bool> = TARGETS.iter()
             .map(|t| theme.targets.contains(&t.to_string()))
             .collect();
 
         let idx = MultiSelect::with_theme(&ColorfulTheme::default())
             .with_prompt("Select targets")
-            .items(all_targets)
+            .items(TARGETS)
             .defaults(&defaults)
             .interact()
             .unwrap();
 
         let sel: Vec<String> = idx.into_iter()
-            .map(|i| all_targets[i].to_string())
+            .map(|i| TARGETS[i].to_string())
             .collect();
 
         theme.targets = sel;
@@ -3,3 +3,6 @@ pub mod colors;
 pub mod utils;
 pub mod generate;
 pub mod templates;
+pub mod osc;
+pub mod export;
+pub mod gallery;
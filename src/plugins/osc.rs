@@ -0,0 +1,83 @@
+use super::parser::Colors;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::{FileTypeExt, MetadataExt};
+
+// Build the OSC 4/10/11/12 sequences that recolor a running terminal
+fn build_sequences(colors: &Colors) -> String {
+    let palette = [
+        &colors.normal.black,
+        &colors.normal.red,
+        &colors.normal.green,
+        &colors.normal.yellow,
+        &colors.normal.blue,
+        &colors.normal.magenta,
+        &colors.normal.cyan,
+        &colors.normal.white,
+        &colors.bright.black,
+        &colors.bright.red,
+        &colors.bright.green,
+        &colors.bright.yellow,
+        &colors.bright.blue,
+        &colors.bright.magenta,
+        &colors.bright.cyan,
+        &colors.bright.white,
+    ];
+
+    let mut seq = String::new();
+    for (i, color) in palette.iter().enumerate() {
+        seq.push_str(&format!("\x1b]4;{};{}\x1b\\", i, to_xparse(color)));
+    }
+    seq.push_str(&format!("\x1b]10;{}\x1b\\", to_xparse(&colors.special.foreground)));
+    seq.push_str(&format!("\x1b]11;{}\x1b\\", to_xparse(&colors.special.background)));
+    seq.push_str(&format!("\x1b]12;{}\x1b\\", to_xparse(&colors.special.cursor)));
+    seq
+}
+
+// Send the sequences to the controlling terminal, and to every PTY
+// owned by the current user when `all_ptys` is set
+pub fn apply_osc(colors: &Colors, all_ptys: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let seq = build_sequences(colors);
+
+    if all_ptys {
+        let uid = fs::metadata("/proc/self")?.uid();
+        let mut count = 0;
+
+        for e in fs::read_dir("/dev/pts")?.flatten() {
+            if e.file_name() == "ptmx" {
+                continue;
+            }
+            let Ok(meta) = e.metadata() else { continue };
+            if !meta.file_type().is_char_device() || meta.uid() != uid {
+                continue;
+            }
+            if write_tty(&e.path().to_string_lossy(), &seq) {
+                count += 1;
+            }
+        }
+
+        println!("  ↻ Recolored {} terminal(s)", count);
+    } else if write_tty("/dev/tty", &seq) {
+        println!("  ↻ Recolored current terminal");
+    } else {
+        println!("  ⚠ Could not recolor terminal (no tty?)");
+    }
+
+    Ok(())
+}
+
+fn write_tty(path: &str, seq: &str) -> bool {
+    match OpenOptions::new().write(true).open(path) {
+        Ok(mut f) => f.write_all(seq.as_bytes()).and_then(|_| f.flush()).is_ok(),
+        Err(_) => false,
+    }
+}
+
+// XParseColor format (rgb:rr/gg/bb), understood by more terminals than #rrggbb
+fn to_xparse(hex: &str) -> String {
+    let hex = hex.trim_start_matches('#');
+    if hex.len() < 6 {
+        return format!("#{}", hex);
+    }
+    format!("rgb:{}/{}/{}", &hex[0..2], &hex[2..4], &hex[4..6])
+}
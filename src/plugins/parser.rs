@@ -10,6 +10,10 @@ pub struct MetadataFile {
 pub struct Metadata {
     pub name: String,
     pub author: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub variants: Vec<String>,
     pub files: Vec<String>,
     pub targets: Vec<String>,
 }
@@ -54,5 +58,35 @@ pub fn parse_metadata(path: &str) -> Result<Metadata, Box<dyn std::error::Error>
 pub fn parse_colors(theme: &str) -> Result<Colors, Box<dyn std::error::Error>> {
     let contents = fs::read_to_string(theme)?;
     let color_file: ColorFile = toml::from_str(&contents)?;
+    check_colors(&color_file.colors)?;
     Ok(color_file.colors)
 }
+
+// Every generator expects full #rrggbb values, so reject anything else here
+fn check_colors(colors: &Colors) -> Result<(), Box<dyn std::error::Error>> {
+    let sets = [("normal", &colors.normal), ("bright", &colors.bright)];
+    let mut fields: Vec<(String, &str)> = Vec::new();
+    for (set_name, set) in sets {
+        for (name, value) in [
+            ("black", &set.black), ("red", &set.red), ("green", &set.green), ("yellow", &set.yellow),
+            ("blue", &set.blue), ("magenta", &set.magenta), ("cyan", &set.cyan), ("white", &set.white),
+        ] {
+            fields.push((format!("{}.{}", set_name, name), value));
+        }
+    }
+    for (name, value) in [
+        ("background", &colors.special.background),
+        ("foreground", &colors.special.foreground),
+        ("cursor", &colors.special.cursor),
+    ] {
+        fields.push((format!("special.{}", name), value));
+    }
+
+    for (name, value) in fields {
+        let hex = value.strip_prefix('#').unwrap_or("");
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("Invalid color '{}' for {} (expected #rrggbb)", value, name).into());
+        }
+    }
+    Ok(())
+}
@@ -1,25 +1,96 @@
+use super::colors::{hex_to_rgb_tuple, relative_luminance};
 use super::parser::Colors;
 
-type ReloadCmd = Option<(String, Vec<String>, String)>;
+pub type ReloadCmd = Option<(String, Vec<String>, String)>;
+
+// Everything a target produces. Generators never write files themselves,
+// so the caller can back up every path before touching it
+pub struct TargetOutput {
+    pub files: Vec<(String, String)>,
+    pub reload: ReloadCmd,
+    // Printed once all files were written
+    pub notes: Vec<String>,
+}
+
+impl From<(String, String, ReloadCmd)> for TargetOutput {
+    fn from((path, content, reload): (String, String, ReloadCmd)) -> Self {
+        TargetOutput { files: vec![(path, content)], reload, notes: Vec::new() }
+    }
+}
+
+// Highlight group as (group, fg, bg, style, sp)
+type Highlight<'a> = (&'a str, Option<&'a str>, Option<&'a str>, Option<&'a str>, Option<&'a str>);
 
 pub fn generate_config_content(
     target: &str,
     colors: &Colors,
     home: &str,
-) -> Result<(String, String, ReloadCmd), Box<dyn std::error::Error>> {
+) -> Result<TargetOutput, Box<dyn std::error::Error>> {
     match target {
-        "kitty" => Ok(generate_kitty_config(colors, home)),
-        "waybar" => Ok(generate_waybar_config(colors, home)),
-        "hyprland" => Ok(generate_hyprland_config(colors, home)),
-        "rofi" => Ok(generate_rofi_config(colors, home)),
-        "dunst" => Ok(generate_dunst_config(colors, home)),
-        "foot" => Ok(generate_foot_config(colors, home)),
-        "neovim" => Ok(generate_neovim_config(colors, home)),
+        "kitty" => Ok(generate_kitty_config(colors, home).into()),
+        "waybar" => Ok(generate_waybar_config(colors, home).into()),
+        "hyprland" => Ok(generate_hyprland_config(colors, home).into()),
+        "rofi" => Ok(generate_rofi_config(colors, home).into()),
+        "dunst" => Ok(generate_dunst_config(colors, home).into()),
+        "foot" => Ok(generate_foot_config(colors, home).into()),
+        "neovim" => Ok(generate_neovim_config(colors, home).into()),
         "gtk" => Ok(generate_gtk_config(colors, home)),
+        "alacritty" => Ok(generate_alacritty_config(colors, home).into()),
+        "wezterm" => Ok(generate_wezterm_config(colors, home)),
+        "ghostty" => Ok(generate_ghostty_config(colors, home).into()),
+        "tmux" => Ok(generate_tmux_config(colors, home).into()),
+        "sway" => Ok(generate_sway_config(colors, home).into()),
+        "i3" => Ok(generate_i3_config(colors, home).into()),
+        "xresources" => Ok(generate_xresources_config(colors, home).into()),
+        "mako" => Ok(generate_mako_config(colors, home).into()),
+        "swaync" => Ok(generate_swaync_config(colors, home).into()),
+        "polybar" => Ok(generate_polybar_config(colors, home).into()),
+        "eww" => Ok(generate_eww_config(colors, home).into()),
+        "helix" => Ok(generate_helix_config(colors, home).into()),
+        "vim" => Ok(generate_vim_config(colors, home).into()),
+        "vscode" => Ok(generate_vscode_config(colors, home)),
+        "emacs" => Ok(generate_emacs_config(colors, home).into()),
+        "bat" => Ok(generate_bat_config(colors, home).into()),
+        "dircolors" => Ok(generate_dircolors_config(colors, home)),
+        "fzf" => Ok(generate_fzf_config(colors, home).into()),
+        "starship" => Ok(generate_starship_config(colors, home).into()),
+        "fish" => Ok(generate_fish_config(colors, home).into()),
+        "btop" => Ok(generate_btop_config(colors, home).into()),
+        "lazygit" => Ok(generate_lazygit_config(colors, home)),
+        "yazi" => Ok(generate_yazi_config(colors, home)),
+        "zathura" => Ok(generate_zathura_config(colors, home).into()),
+        "qt" => Ok(generate_qt_config(colors, home)),
+        "firefox" => Ok(generate_firefox_config(colors, home)),
+        "vesktop" => Ok(generate_vesktop_config(colors, home)),
+        "obsidian" => Ok(generate_obsidian_config(colors, home)),
+        "spicetify" => Ok(generate_spicetify_config(colors, home)),
+        "hyprlock" => Ok(generate_hyprlock_config(colors, home).into()),
+        "swaylock" => Ok(generate_swaylock_config(colors, home).into()),
+        "fuzzel" => Ok(generate_fuzzel_config(colors, home).into()),
+        "wofi" => Ok(generate_wofi_config(colors, home)),
         _ => Err(format!("Unknown target: {}", target).into()),
     }
 }
 
+// How to undo a target's reload once its files are restored. Most apps just
+// re-read the restored files; the rest keep state elsewhere, captured here
+// before the new theme is applied
+pub fn revert_command(target: &str, output: &TargetOutput) -> ReloadCmd {
+    match target {
+        "gtk" => gtk_revert(),
+        "xresources" => xresources_revert(),
+        "emacs" => emacs_revert(&output.files[0].0),
+        "fish" => fish_revert(&output.files[0].1),
+        _ => output.reload.clone(),
+    }
+}
+
+// Output of a command that only reads state, if it succeeded
+fn query(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(cmd).args(args).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
 fn generate_kitty_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
     let path = format!("{}/.config/kitty/colors.conf", home);
     let content = format!(
@@ -128,24 +199,2913 @@ fn generate_waybar_config(colors: &Colors, home: &str) -> (String, String, Reloa
     );
 
     let reload = Some((
-        "sh".to_string(),
-        vec![
-            "-c".to_string(),
-            "pkill -x waybar; waybar >/dev/null 2>&1 & disown".to_string()
-        ],
-        "waybar".to_string(),
+        "sh".to_string(),
+        vec![
+            "-c".to_string(),
+            "pkill -x waybar; waybar >/dev/null 2>&1 & disown".to_string()
+        ],
+        "waybar".to_string(),
+    ));
+
+    (path, content, reload)
+}
+
+fn generate_hyprland_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/hypr/colors.conf", home);
+    let content = format!(
+        "# Hyprland colors - Generated by themey
+$background = rgb({background})
+$foreground = rgb({foreground})
+$cursor = rgb({cursor})
+
+$black = rgb({black})
+$red = rgb({red})
+$green = rgb({green})
+$yellow = rgb({yellow})
+$blue = rgb({blue})
+$magenta = rgb({magenta})
+$cyan = rgb({cyan})
+$white = rgb({white})
+
+$bright_black = rgb({bright_black})
+$bright_red = rgb({bright_red})
+$bright_green = rgb({bright_green})
+$bright_yellow = rgb({bright_yellow})
+$bright_blue = rgb({bright_blue})
+$bright_magenta = rgb({bright_magenta})
+$bright_cyan = rgb({bright_cyan})
+$bright_white = rgb({bright_white})
+
+# Border colors
+general {{
+    col.active_border = $cyan $blue 45deg
+    col.inactive_border = $black
+}}
+",
+        background = hex_to_rgb(&colors.special.background),
+        foreground = hex_to_rgb(&colors.special.foreground),
+        cursor = hex_to_rgb(&colors.special.cursor),
+        black = hex_to_rgb(&colors.normal.black),
+        red = hex_to_rgb(&colors.normal.red),
+        green = hex_to_rgb(&colors.normal.green),
+        yellow = hex_to_rgb(&colors.normal.yellow),
+        blue = hex_to_rgb(&colors.normal.blue),
+        magenta = hex_to_rgb(&colors.normal.magenta),
+        cyan = hex_to_rgb(&colors.normal.cyan),
+        white = hex_to_rgb(&colors.normal.white),
+        bright_black = hex_to_rgb(&colors.bright.black),
+        bright_red = hex_to_rgb(&colors.bright.red),
+        bright_green = hex_to_rgb(&colors.bright.green),
+        bright_yellow = hex_to_rgb(&colors.bright.yellow),
+        bright_blue = hex_to_rgb(&colors.bright.blue),
+        bright_magenta = hex_to_rgb(&colors.bright.magenta),
+        bright_cyan = hex_to_rgb(&colors.bright.cyan),
+        bright_white = hex_to_rgb(&colors.bright.white),
+    );
+
+    let reload = Some((
+        "hyprctl".to_string(),
+        vec!["reload".to_string()],
+        "hyprland".to_string(),
+    ));
+
+    (path, content, reload)
+}
+
+fn generate_rofi_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/rofi/colors.rasi", home);
+    let content = format!(
+        "/* Rofi colors - Generated by themey */
+* {{
+    background: {background};
+    background-alt: {bright_black};
+    foreground: {foreground};
+    border: {blue};
+    cursor: {cursor};
+
+    black: {black};
+    red: {red};
+    green: {green};
+    yellow: {yellow};
+    blue: {blue};
+    magenta: {magenta};
+    cyan: {cyan};
+    white: {white};
+
+    bright-black: {bright_black};
+    bright-red: {bright_red};
+    bright-green: {bright_green};
+    bright-yellow: {bright_yellow};
+    bright-blue: {bright_blue};
+    bright-magenta: {bright_magenta};
+    bright-cyan: {bright_cyan};
+    bright-white: {bright_white};
+
+    background-color: @background;
+    text-color: @foreground;
+    selected-normal-background: @blue;
+    selected-normal-foreground: @background;
+}}
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        cursor = colors.special.cursor,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+        white = colors.normal.white,
+        bright_black = colors.bright.black,
+        bright_red = colors.bright.red,
+        bright_green = colors.bright.green,
+        bright_yellow = colors.bright.yellow,
+        bright_blue = colors.bright.blue,
+        bright_magenta = colors.bright.magenta,
+        bright_cyan = colors.bright.cyan,
+        bright_white = colors.bright.white,
+    );
+
+    (path, content, None)
+}
+
+fn generate_dunst_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/dunst/dunstrc.d/colors.conf", home);
+    let content = format!(
+        "# Dunst colors - Generated by themey
+[urgency_low]
+background = \"{background}\"
+foreground = \"{foreground}\"
+frame_color = \"{blue}\"
+
+[urgency_normal]
+background = \"{background}\"
+foreground = \"{foreground}\"
+frame_color = \"{cyan}\"
+
+[urgency_critical]
+background = \"{background}\"
+foreground = \"{foreground}\"
+frame_color = \"{red}\"
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        blue = colors.normal.blue,
+        cyan = colors.normal.cyan,
+        red = colors.normal.red,
+    );
+
+    (path, content, None)
+}
+
+fn generate_foot_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/foot/colors.ini", home);
+    let content = format!(
+        "# Foot colors - Generated by themey
+[colors]
+foreground={foreground}
+background={background}
+cursor={background} {cursor}
+
+## Normal/regular colors (color palette 0-7)
+regular0={black}
+regular1={red}
+regular2={green}
+regular3={yellow}
+regular4={blue}
+regular5={magenta}
+regular6={cyan}
+regular7={white}
+
+## Bright colors (color palette 8-15)
+bright0={bright_black}
+bright1={bright_red}
+bright2={bright_green}
+bright3={bright_yellow}
+bright4={bright_blue}
+bright5={bright_magenta}
+bright6={bright_cyan}
+bright7={bright_white}
+",
+        foreground = strip_hash(&colors.special.foreground),
+        background = strip_hash(&colors.special.background),
+        cursor = strip_hash(&colors.special.cursor),
+        black = strip_hash(&colors.normal.black),
+        red = strip_hash(&colors.normal.red),
+        green = strip_hash(&colors.normal.green),
+        yellow = strip_hash(&colors.normal.yellow),
+        blue = strip_hash(&colors.normal.blue),
+        magenta = strip_hash(&colors.normal.magenta),
+        cyan = strip_hash(&colors.normal.cyan),
+        white = strip_hash(&colors.normal.white),
+        bright_black = strip_hash(&colors.bright.black),
+        bright_red = strip_hash(&colors.bright.red),
+        bright_green = strip_hash(&colors.bright.green),
+        bright_yellow = strip_hash(&colors.bright.yellow),
+        bright_blue = strip_hash(&colors.bright.blue),
+        bright_magenta = strip_hash(&colors.bright.magenta),
+        bright_cyan = strip_hash(&colors.bright.cyan),
+        bright_white = strip_hash(&colors.bright.white),
+    );
+
+    // Foot doesn't support hot-reloading, user needs to restart instances
+    // (or use `themey use --osc` to recolor running terminals)
+    (path, content, None)
+}
+
+fn generate_alacritty_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/alacritty/themey.toml", home);
+    let content = format!(
+        "# Alacritty colors - Generated by themey
+# Import this in your alacritty.toml with:
+# [general]
+# import = [\"~/.config/alacritty/themey.toml\"]
+
+[colors.primary]
+background = \"{background}\"
+foreground = \"{foreground}\"
+
+[colors.cursor]
+text = \"{background}\"
+cursor = \"{cursor}\"
+
+[colors.selection]
+text = \"{foreground}\"
+background = \"{bright_black}\"
+
+[colors.normal]
+black = \"{black}\"
+red = \"{red}\"
+green = \"{green}\"
+yellow = \"{yellow}\"
+blue = \"{blue}\"
+magenta = \"{magenta}\"
+cyan = \"{cyan}\"
+white = \"{white}\"
+
+[colors.bright]
+black = \"{bright_black}\"
+red = \"{bright_red}\"
+green = \"{bright_green}\"
+yellow = \"{bright_yellow}\"
+blue = \"{bright_blue}\"
+magenta = \"{bright_magenta}\"
+cyan = \"{bright_cyan}\"
+white = \"{bright_white}\"
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        cursor = colors.special.cursor,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+        white = colors.normal.white,
+        bright_black = colors.bright.black,
+        bright_red = colors.bright.red,
+        bright_green = colors.bright.green,
+        bright_yellow = colors.bright.yellow,
+        bright_blue = colors.bright.blue,
+        bright_magenta = colors.bright.magenta,
+        bright_cyan = colors.bright.cyan,
+        bright_white = colors.bright.white,
+    );
+
+    // Alacritty watches its config files and reloads them on change
+    (path, content, None)
+}
+
+fn generate_wezterm_config(colors: &Colors, home: &str) -> TargetOutput {
+    let path = format!("{}/.config/wezterm/colors/themey.toml", home);
+    let content = format!(
+        "# WezTerm color scheme - Generated by themey
+# Use it in your wezterm.lua with: config.color_scheme = \"Themey\"
+
+[metadata]
+name = \"Themey\"
+
+[colors]
+foreground = \"{foreground}\"
+background = \"{background}\"
+cursor_bg = \"{cursor}\"
+cursor_border = \"{cursor}\"
+cursor_fg = \"{background}\"
+selection_bg = \"{bright_black}\"
+selection_fg = \"{foreground}\"
+
+ansi = [
+    \"{black}\",
+    \"{red}\",
+    \"{green}\",
+    \"{yellow}\",
+    \"{blue}\",
+    \"{magenta}\",
+    \"{cyan}\",
+    \"{white}\",
+]
+
+brights = [
+    \"{bright_black}\",
+    \"{bright_red}\",
+    \"{bright_green}\",
+    \"{bright_yellow}\",
+    \"{bright_blue}\",
+    \"{bright_magenta}\",
+    \"{bright_cyan}\",
+    \"{bright_white}\",
+]
+",
+        foreground = colors.special.foreground,
+        background = colors.special.background,
+        cursor = colors.special.cursor,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+        white = colors.normal.white,
+        bright_black = colors.bright.black,
+        bright_red = colors.bright.red,
+        bright_green = colors.bright.green,
+        bright_yellow = colors.bright.yellow,
+        bright_blue = colors.bright.blue,
+        bright_magenta = colors.bright.magenta,
+        bright_cyan = colors.bright.cyan,
+        bright_white = colors.bright.white,
+    );
+
+    // Also expose the palette as a Lua module for use in wezterm.lua
+    let lua_content = format!(
+        "-- WezTerm palette - Generated by themey
+-- Use it in your wezterm.lua with: local themey = require('themey')
+
+return {{
+  background = '{background}',
+  foreground = '{foreground}',
+  cursor = '{cursor}',
+
+  black = '{black}',
+  red = '{red}',
+  green = '{green}',
+  yellow = '{yellow}',
+  blue = '{blue}',
+  magenta = '{magenta}',
+  cyan = '{cyan}',
+  white = '{white}',
+
+  bright_black = '{bright_black}',
+  bright_red = '{bright_red}',
+  bright_green = '{bright_green}',
+  bright_yellow = '{bright_yellow}',
+  bright_blue = '{bright_blue}',
+  bright_magenta = '{bright_magenta}',
+  bright_cyan = '{bright_cyan}',
+  bright_white = '{bright_white}',
+}}
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        cursor = colors.special.cursor,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+        white = colors.normal.white,
+        bright_black = colors.bright.black,
+        bright_red = colors.bright.red,
+        bright_green = colors.bright.green,
+        bright_yellow = colors.bright.yellow,
+        bright_blue = colors.bright.blue,
+        bright_magenta = colors.bright.magenta,
+        bright_cyan = colors.bright.cyan,
+        bright_white = colors.bright.white,
+    );
+    // WezTerm hot-reloads its config, so no reload command is needed
+    TargetOutput {
+        files: vec![
+            (path, content),
+            (format!("{}/.config/wezterm/themey.lua", home), lua_content),
+        ],
+        reload: None,
+        notes: Vec::new(),
+    }
+}
+
+fn generate_ghostty_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/ghostty/themes/themey", home);
+    let content = format!(
+        "# Ghostty theme - Generated by themey
+# Use it in your ghostty config with: theme = themey
+
+background = {background}
+foreground = {foreground}
+cursor-color = {cursor}
+cursor-text = {background}
+selection-background = {bright_black}
+selection-foreground = {foreground}
+
+palette = 0={black}
+palette = 1={red}
+palette = 2={green}
+palette = 3={yellow}
+palette = 4={blue}
+palette = 5={magenta}
+palette = 6={cyan}
+palette = 7={white}
+palette = 8={bright_black}
+palette = 9={bright_red}
+palette = 10={bright_green}
+palette = 11={bright_yellow}
+palette = 12={bright_blue}
+palette = 13={bright_magenta}
+palette = 14={bright_cyan}
+palette = 15={bright_white}
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        cursor = colors.special.cursor,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+        white = colors.normal.white,
+        bright_black = colors.bright.black,
+        bright_red = colors.bright.red,
+        bright_green = colors.bright.green,
+        bright_yellow = colors.bright.yellow,
+        bright_blue = colors.bright.blue,
+        bright_magenta = colors.bright.magenta,
+        bright_cyan = colors.bright.cyan,
+        bright_white = colors.bright.white,
+    );
+
+    // Ghostty reloads its config on SIGUSR2
+    let reload = Some((
+        "pkill".to_string(),
+        vec!["-USR2".to_string(), "-x".to_string(), "ghostty".to_string()],
+        "ghostty".to_string(),
+    ));
+
+    (path, content, reload)
+}
+
+fn generate_tmux_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/tmux/themey.conf", home);
+    let content = format!(
+        "# tmux colors - Generated by themey
+# Source this in your tmux.conf with: source-file ~/.config/tmux/themey.conf
+
+# Status bar
+set -g status-style \"bg={background},fg={foreground}\"
+set -g status-left-style \"bg={blue},fg={background},bold\"
+set -g status-right-style \"bg={black},fg={foreground}\"
+set -g window-status-style \"fg={bright_black}\"
+set -g window-status-current-style \"fg={blue},bold\"
+set -g window-status-activity-style \"fg={yellow}\"
+set -g window-status-bell-style \"fg={red},bold\"
+
+# Pane borders
+set -g pane-border-style \"fg={bright_black}\"
+set -g pane-active-border-style \"fg={blue}\"
+set -g display-panes-colour \"{bright_black}\"
+set -g display-panes-active-colour \"{blue}\"
+
+# Messages
+set -g message-style \"bg={black},fg={foreground}\"
+set -g message-command-style \"bg={black},fg={cyan}\"
+
+# Copy mode
+set -g mode-style \"bg={bright_black},fg={foreground}\"
+set -gq copy-mode-match-style \"bg={yellow},fg={background}\"
+set -gq copy-mode-current-match-style \"bg={bright_yellow},fg={background}\"
+
+set -g clock-mode-colour \"{blue}\"
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        cyan = colors.normal.cyan,
+        bright_black = colors.bright.black,
+        bright_yellow = colors.bright.yellow,
+    );
+
+    // Re-source the file on every running tmux server
+    let reload = Some((
+        "sh".to_string(),
+        vec![
+            "-c".to_string(),
+            format!(
+                "for s in \"${{TMUX_TMPDIR:-/tmp}}/tmux-$(id -u)\"/*; do \
+                 [ -S \"$s\" ] && tmux -S \"$s\" source-file '{}'; \
+                 done; true",
+                path
+            ),
+        ],
+        "tmux".to_string(),
+    ));
+
+    (path, content, reload)
+}
+
+fn generate_sway_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/sway/themey", home);
+    let content = format!(
+        "# Sway colors - Generated by themey
+# Include this in your sway config with: include ~/.config/sway/themey
+{}",
+        wm_colors(colors)
+    );
+
+    let reload = Some((
+        "swaymsg".to_string(),
+        vec!["reload".to_string()],
+        "sway".to_string(),
+    ));
+
+    (path, content, reload)
+}
+
+fn generate_i3_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/i3/themey", home);
+    let content = format!(
+        "# i3 colors - Generated by themey
+# Include this in your i3 config with: include ~/.config/i3/themey
+{}",
+        wm_colors(colors)
+    );
+
+    let reload = Some((
+        "i3-msg".to_string(),
+        vec!["reload".to_string()],
+        "i3".to_string(),
+    ));
+
+    (path, content, reload)
+}
+
+// Shared by sway and i3, which use the same config syntax
+fn wm_colors(colors: &Colors) -> String {
+    format!(
+        "
+set $background {background}
+set $foreground {foreground}
+set $cursor {cursor}
+
+set $black {black}
+set $red {red}
+set $green {green}
+set $yellow {yellow}
+set $blue {blue}
+set $magenta {magenta}
+set $cyan {cyan}
+set $white {white}
+
+set $bright_black {bright_black}
+set $bright_red {bright_red}
+set $bright_green {bright_green}
+set $bright_yellow {bright_yellow}
+set $bright_blue {bright_blue}
+set $bright_magenta {bright_magenta}
+set $bright_cyan {bright_cyan}
+set $bright_white {bright_white}
+
+# class                 border         background     text           indicator      child_border
+client.focused          $blue          $blue          $background    $cyan          $blue
+client.focused_inactive $bright_black  $black         $foreground    $bright_black  $bright_black
+client.unfocused        $black         $background    $bright_black  $black         $black
+client.urgent           $red           $red           $background    $red           $red
+client.placeholder      $black         $background    $foreground    $black         $black
+client.background       $background
+
+# Bar colors. A bar block here would start a second bar, so use these
+# in your own bar block instead:
+#
+# bar {{
+#     colors {{
+#         background $bar_background
+#         statusline $bar_statusline
+#         separator $bar_separator
+#         focused_workspace  $blue $blue $background
+#         active_workspace   $bright_black $bright_black $foreground
+#         inactive_workspace $background $background $bright_black
+#         urgent_workspace   $red $red $background
+#     }}
+# }}
+set $bar_background $background
+set $bar_statusline $foreground
+set $bar_separator $bright_black
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        cursor = colors.special.cursor,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+        white = colors.normal.white,
+        bright_black = colors.bright.black,
+        bright_red = colors.bright.red,
+        bright_green = colors.bright.green,
+        bright_yellow = colors.bright.yellow,
+        bright_blue = colors.bright.blue,
+        bright_magenta = colors.bright.magenta,
+        bright_cyan = colors.bright.cyan,
+        bright_white = colors.bright.white,
+    )
+}
+
+fn generate_xresources_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/themey/Xresources", home);
+    let content = format!(
+        "! Xresources colors - Generated by themey
+! Merge this with: xrdb -merge ~/.config/themey/Xresources
+
+*foreground: {foreground}
+*background: {background}
+*cursorColor: {cursor}
+
+! Normal colors
+*color0: {black}
+*color1: {red}
+*color2: {green}
+*color3: {yellow}
+*color4: {blue}
+*color5: {magenta}
+*color6: {cyan}
+*color7: {white}
+
+! Bright colors
+*color8: {bright_black}
+*color9: {bright_red}
+*color10: {bright_green}
+*color11: {bright_yellow}
+*color12: {bright_blue}
+*color13: {bright_magenta}
+*color14: {bright_cyan}
+*color15: {bright_white}
+",
+        foreground = colors.special.foreground,
+        background = colors.special.background,
+        cursor = colors.special.cursor,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+        white = colors.normal.white,
+        bright_black = colors.bright.black,
+        bright_red = colors.bright.red,
+        bright_green = colors.bright.green,
+        bright_yellow = colors.bright.yellow,
+        bright_blue = colors.bright.blue,
+        bright_magenta = colors.bright.magenta,
+        bright_cyan = colors.bright.cyan,
+        bright_white = colors.bright.white,
+    );
+
+    // Only merge when there's an X display to merge into
+    let reload = Some((
+        "sh".to_string(),
+        vec![
+            "-c".to_string(),
+            format!("[ -n \"$DISPLAY\" ] && xrdb -merge '{}'", path),
+        ],
+        "xrdb".to_string(),
+    ));
+
+    (path, content, reload)
+}
+
+// Reload the whole resource database as it was, merging can't undo itself
+fn xresources_revert() -> ReloadCmd {
+    std::env::var_os("DISPLAY")?;
+    let resources = query("xrdb", &["-query"])?;
+
+    Some((
+        "sh".to_string(),
+        vec![
+            "-c".to_string(),
+            "printf '%s' \"$1\" | xrdb -load".to_string(),
+            "sh".to_string(),
+            resources,
+        ],
+        "xrdb".to_string(),
+    ))
+}
+
+fn generate_mako_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/mako/themey", home);
+    let content = format!(
+        "# Mako colors - Generated by themey
+# Include this in your mako config with: include=~/.config/mako/themey
+background-color={background}
+text-color={foreground}
+border-color={cyan}
+progress-color=over {bright_black}
+
+[urgency=low]
+border-color={blue}
+
+[urgency=normal]
+border-color={cyan}
+
+[urgency=critical]
+border-color={red}
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        bright_black = colors.bright.black,
+        blue = colors.normal.blue,
+        cyan = colors.normal.cyan,
+        red = colors.normal.red,
+    );
+
+    let reload = Some((
+        "makoctl".to_string(),
+        vec!["reload".to_string()],
+        "mako".to_string(),
+    ));
+
+    (path, content, reload)
+}
+
+fn generate_swaync_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/swaync/themey.css", home);
+    let content = format!(
+        "/* SwayNC colors - Generated by themey */
+/* Import this in your style.css with: @import \"themey.css\"; */
+
+@define-color themey_background {background};
+@define-color themey_foreground {foreground};
+@define-color themey_surface {black};
+@define-color themey_muted {bright_black};
+
+/* Notification frames by urgency */
+@define-color themey_low {blue};
+@define-color themey_normal {cyan};
+@define-color themey_critical {red};
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        black = colors.normal.black,
+        bright_black = colors.bright.black,
+        blue = colors.normal.blue,
+        cyan = colors.normal.cyan,
+        red = colors.normal.red,
+    );
+
+    let reload = Some((
+        "swaync-client".to_string(),
+        vec!["-rs".to_string()],
+        "swaync".to_string(),
+    ));
+
+    (path, content, reload)
+}
+
+fn generate_polybar_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/polybar/themey.ini", home);
+    let content = format!(
+        "; Polybar colors - Generated by themey
+; Include this in your config.ini with: include-file = ~/.config/polybar/themey.ini
+; and reference colors as ${{colors.background}}, ${{colors.blue}}, etc.
+
+[colors]
+background = {background}
+background-alt = {black}
+foreground = {foreground}
+primary = {blue}
+secondary = {cyan}
+alert = {red}
+disabled = {bright_black}
+
+black = {black}
+red = {red}
+green = {green}
+yellow = {yellow}
+blue = {blue}
+magenta = {magenta}
+cyan = {cyan}
+white = {white}
+
+bright-black = {bright_black}
+bright-red = {bright_red}
+bright-green = {bright_green}
+bright-yellow = {bright_yellow}
+bright-blue = {bright_blue}
+bright-magenta = {bright_magenta}
+bright-cyan = {bright_cyan}
+bright-white = {bright_white}
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+        white = colors.normal.white,
+        bright_black = colors.bright.black,
+        bright_red = colors.bright.red,
+        bright_green = colors.bright.green,
+        bright_yellow = colors.bright.yellow,
+        bright_blue = colors.bright.blue,
+        bright_magenta = colors.bright.magenta,
+        bright_cyan = colors.bright.cyan,
+        bright_white = colors.bright.white,
+    );
+
+    let reload = Some((
+        "polybar-msg".to_string(),
+        vec!["cmd".to_string(), "restart".to_string()],
+        "polybar".to_string(),
+    ));
+
+    (path, content, reload)
+}
+
+fn generate_eww_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/eww/themey.scss", home);
+    let content = format!(
+        "// Eww colors - Generated by themey
+// Import this in your eww.scss with: @import \"themey\";
+
+$background: {background};
+$foreground: {foreground};
+$cursor: {cursor};
+
+$black: {black};
+$red: {red};
+$green: {green};
+$yellow: {yellow};
+$blue: {blue};
+$magenta: {magenta};
+$cyan: {cyan};
+$white: {white};
+
+$bright-black: {bright_black};
+$bright-red: {bright_red};
+$bright-green: {bright_green};
+$bright-yellow: {bright_yellow};
+$bright-blue: {bright_blue};
+$bright-magenta: {bright_magenta};
+$bright-cyan: {bright_cyan};
+$bright-white: {bright_white};
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        cursor = colors.special.cursor,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+        white = colors.normal.white,
+        bright_black = colors.bright.black,
+        bright_red = colors.bright.red,
+        bright_green = colors.bright.green,
+        bright_yellow = colors.bright.yellow,
+        bright_blue = colors.bright.blue,
+        bright_magenta = colors.bright.magenta,
+        bright_cyan = colors.bright.cyan,
+        bright_white = colors.bright.white,
+    );
+
+    let reload = Some((
+        "eww".to_string(),
+        vec!["reload".to_string()],
+        "eww".to_string(),
+    ));
+
+    (path, content, reload)
+}
+
+fn generate_helix_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/helix/themes/themey.toml", home);
+    let content = format!(
+        "# Helix theme - Generated by themey
+# Use it in your config.toml with: theme = \"themey\"
+
+# Editor
+\"ui.background\" = {{ bg = \"background\" }}
+\"ui.text\" = \"foreground\"
+\"ui.text.focus\" = {{ fg = \"foreground\", modifiers = [\"bold\"] }}
+\"ui.cursor\" = {{ fg = \"background\", bg = \"cursor\" }}
+\"ui.cursor.primary\" = {{ fg = \"background\", bg = \"cursor\" }}
+\"ui.cursor.match\" = {{ fg = \"bright_cyan\", modifiers = [\"bold\"] }}
+\"ui.cursorline.primary\" = {{ bg = \"black\" }}
+\"ui.cursorcolumn.primary\" = {{ bg = \"black\" }}
+\"ui.linenr\" = \"bright_black\"
+\"ui.linenr.selected\" = {{ fg = \"yellow\", modifiers = [\"bold\"] }}
+\"ui.gutter\" = {{ bg = \"background\" }}
+\"ui.virtual.ruler\" = {{ bg = \"black\" }}
+\"ui.virtual.whitespace\" = \"bright_black\"
+\"ui.virtual.indent-guide\" = \"bright_black\"
+\"ui.window\" = \"bright_black\"
+\"ui.selection\" = {{ bg = \"bright_black\" }}
+\"ui.highlight\" = {{ bg = \"bright_black\" }}
+
+# Statusline
+\"ui.statusline\" = {{ fg = \"foreground\", bg = \"black\" }}
+\"ui.statusline.inactive\" = {{ fg = \"bright_black\", bg = \"black\" }}
+
+# Tabs
+\"ui.bufferline\" = {{ fg = \"bright_black\", bg = \"black\" }}
+\"ui.bufferline.active\" = {{ fg = \"foreground\", bg = \"background\", modifiers = [\"bold\"] }}
+\"ui.bufferline.background\" = {{ bg = \"black\" }}
+
+# Popups and menus
+\"ui.popup\" = {{ fg = \"foreground\", bg = \"black\" }}
+\"ui.help\" = {{ fg = \"foreground\", bg = \"black\" }}
+\"ui.menu\" = {{ fg = \"foreground\", bg = \"black\" }}
+\"ui.menu.selected\" = {{ fg = \"background\", bg = \"blue\" }}
+\"ui.menu.scroll\" = {{ fg = \"white\", bg = \"bright_black\" }}
+
+# Syntax highlighting
+\"comment\" = {{ fg = \"bright_black\", modifiers = [\"italic\"] }}
+\"constant\" = \"magenta\"
+\"constant.numeric\" = \"magenta\"
+\"constant.builtin\" = \"magenta\"
+\"constant.character\" = \"green\"
+\"constant.character.escape\" = \"cyan\"
+\"string\" = \"green\"
+\"string.regexp\" = \"green\"
+\"string.special.url\" = {{ fg = \"blue\", underline = {{ style = \"line\" }} }}
+
+\"variable\" = \"foreground\"
+\"variable.builtin\" = \"cyan\"
+\"variable.parameter\" = \"cyan\"
+\"variable.other.member\" = \"cyan\"
+\"function\" = \"blue\"
+\"function.builtin\" = \"blue\"
+\"function.macro\" = \"blue\"
+\"constructor\" = \"blue\"
+
+\"keyword\" = \"red\"
+\"keyword.control\" = \"red\"
+\"keyword.function\" = \"red\"
+\"keyword.operator\" = \"red\"
+\"keyword.control.import\" = \"blue\"
+\"keyword.directive\" = \"yellow\"
+\"keyword.storage\" = \"yellow\"
+\"label\" = \"red\"
+\"operator\" = \"foreground\"
+
+\"type\" = \"yellow\"
+\"type.builtin\" = \"yellow\"
+\"namespace\" = \"yellow\"
+
+\"special\" = \"cyan\"
+\"attribute\" = \"cyan\"
+\"tag\" = \"red\"
+\"punctuation\" = \"foreground\"
+\"punctuation.delimiter\" = \"foreground\"
+\"punctuation.bracket\" = \"foreground\"
+\"punctuation.special\" = \"cyan\"
+
+\"markup.heading\" = {{ fg = \"blue\", modifiers = [\"bold\"] }}
+\"markup.bold\" = {{ modifiers = [\"bold\"] }}
+\"markup.italic\" = {{ modifiers = [\"italic\"] }}
+\"markup.strikethrough\" = {{ modifiers = [\"crossed_out\"] }}
+\"markup.link.url\" = {{ fg = \"blue\", underline = {{ style = \"line\" }} }}
+\"markup.raw\" = \"green\"
+
+# Diff
+\"diff.plus\" = \"green\"
+\"diff.delta\" = \"yellow\"
+\"diff.minus\" = \"red\"
+
+# Diagnostics
+\"error\" = \"red\"
+\"warning\" = \"yellow\"
+\"info\" = \"blue\"
+\"hint\" = \"cyan\"
+\"diagnostic.error\" = {{ underline = {{ color = \"red\", style = \"line\" }} }}
+\"diagnostic.warning\" = {{ underline = {{ color = \"yellow\", style = \"line\" }} }}
+\"diagnostic.info\" = {{ underline = {{ color = \"blue\", style = \"line\" }} }}
+\"diagnostic.hint\" = {{ underline = {{ color = \"cyan\", style = \"line\" }} }}
+
+[palette]
+background = \"{background}\"
+foreground = \"{foreground}\"
+cursor = \"{cursor}\"
+
+black = \"{black}\"
+red = \"{red}\"
+green = \"{green}\"
+yellow = \"{yellow}\"
+blue = \"{blue}\"
+magenta = \"{magenta}\"
+cyan = \"{cyan}\"
+white = \"{white}\"
+
+bright_black = \"{bright_black}\"
+bright_red = \"{bright_red}\"
+bright_green = \"{bright_green}\"
+bright_yellow = \"{bright_yellow}\"
+bright_blue = \"{bright_blue}\"
+bright_magenta = \"{bright_magenta}\"
+bright_cyan = \"{bright_cyan}\"
+bright_white = \"{bright_white}\"
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        cursor = colors.special.cursor,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+        white = colors.normal.white,
+        bright_black = colors.bright.black,
+        bright_red = colors.bright.red,
+        bright_green = colors.bright.green,
+        bright_yellow = colors.bright.yellow,
+        bright_blue = colors.bright.blue,
+        bright_magenta = colors.bright.magenta,
+        bright_cyan = colors.bright.cyan,
+        bright_white = colors.bright.white,
+    );
+
+    // Helix reloads its config (and theme) on SIGUSR1
+    let reload = Some((
+        "pkill".to_string(),
+        vec!["-USR1".to_string(), "-x".to_string(), "hx".to_string()],
+        "helix".to_string(),
+    ));
+
+    (path, content, reload)
+}
+
+fn generate_vim_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.vim/colors/themey.vim", home);
+
+    let background = colors.special.background.as_str();
+    let foreground = colors.special.foreground.as_str();
+    let cursor = colors.special.cursor.as_str();
+    let black = colors.normal.black.as_str();
+    let red = colors.normal.red.as_str();
+    let green = colors.normal.green.as_str();
+    let yellow = colors.normal.yellow.as_str();
+    let blue = colors.normal.blue.as_str();
+    let magenta = colors.normal.magenta.as_str();
+    let cyan = colors.normal.cyan.as_str();
+    let white = colors.normal.white.as_str();
+    let bright_black = colors.bright.black.as_str();
+    let bright_red = colors.bright.red.as_str();
+    let bright_yellow = colors.bright.yellow.as_str();
+    let bright_cyan = colors.bright.cyan.as_str();
+
+    // Same highlight groups as the neovim colorscheme
+    let groups: &[Highlight] = &[
+        // Editor highlights
+        ("Normal", Some(foreground), Some(background), None, None),
+        ("NormalFloat", Some(foreground), Some(black), None, None),
+        ("FloatBorder", Some(bright_black), Some(black), None, None),
+        ("Cursor", Some(background), Some(cursor), None, None),
+        ("CursorLine", None, Some(black), None, None),
+        ("CursorColumn", None, Some(black), None, None),
+        ("LineNr", Some(bright_black), None, None, None),
+        ("CursorLineNr", Some(yellow), None, Some("bold"), None),
+        ("SignColumn", None, Some(background), None, None),
+        ("ColorColumn", None, Some(black), None, None),
+        ("VertSplit", Some(bright_black), None, None, None),
+        ("Visual", None, Some(bright_black), None, None),
+        ("VisualNOS", None, Some(bright_black), None, None),
+        ("Search", Some(background), Some(yellow), None, None),
+        ("IncSearch", Some(background), Some(bright_yellow), None, None),
+        ("MatchParen", Some(bright_cyan), None, Some("bold"), None),
+        ("Folded", Some(bright_black), Some(black), None, None),
+        ("FoldColumn", Some(bright_black), Some(background), None, None),
+        // Statusline
+        ("StatusLine", Some(foreground), Some(black), None, None),
+        ("StatusLineNC", Some(bright_black), Some(black), None, None),
+        ("WildMenu", Some(background), Some(blue), None, None),
+        // Tabs
+        ("TabLine", Some(bright_black), Some(black), None, None),
+        ("TabLineFill", None, Some(black), None, None),
+        ("TabLineSel", Some(foreground), Some(background), Some("bold"), None),
+        // Popups and menus
+        ("Pmenu", Some(foreground), Some(black), None, None),
+        ("PmenuSel", Some(background), Some(blue), None, None),
+        ("PmenuSbar", None, Some(bright_black), None, None),
+        ("PmenuThumb", None, Some(white), None, None),
+        // Syntax highlighting
+        ("Comment", Some(bright_black), None, Some("italic"), None),
+        ("Constant", Some(magenta), None, None, None),
+        ("String", Some(green), None, None, None),
+        ("Character", Some(green), None, None, None),
+        ("Number", Some(magenta), None, None, None),
+        ("Boolean", Some(magenta), None, None, None),
+        ("Float", Some(magenta), None, None, None),
+        ("Identifier", Some(cyan), None, None, None),
+        ("Function", Some(blue), None, None, None),
+        ("Statement", Some(red), None, None, None),
+        ("Conditional", Some(red), None, None, None),
+        ("Repeat", Some(red), None, None, None),
+        ("Label", Some(red), None, None, None),
+        ("Operator", Some(foreground), None, None, None),
+        ("Keyword", Some(red), None, None, None),
+        ("Exception", Some(red), None, None, None),
+        ("PreProc", Some(yellow), None, None, None),
+        ("Include", Some(blue), None, None, None),
+        ("Define", Some(yellow), None, None, None),
+        ("Macro", Some(yellow), None, None, None),
+        ("PreCondit", Some(yellow), None, None, None),
+        ("Type", Some(yellow), None, None, None),
+        ("StorageClass", Some(yellow), None, None, None),
+        ("Structure", Some(yellow), None, None, None),
+        ("Typedef", Some(yellow), None, None, None),
+        ("Special", Some(cyan), None, None, None),
+        ("SpecialChar", Some(cyan), None, None, None),
+        ("Tag", Some(cyan), None, None, None),
+        ("Delimiter", Some(foreground), None, None, None),
+        ("SpecialComment", Some(bright_black), None, Some("italic"), None),
+        ("Debug", Some(red), None, None, None),
+        ("Underlined", Some(blue), None, Some("underline"), None),
+        ("Ignore", Some(bright_black), None, None, None),
+        ("Error", Some(bright_red), Some(background), None, None),
+        ("Todo", Some(yellow), Some(background), Some("bold"), None),
+        // Diff
+        ("DiffAdd", Some(green), Some(black), None, None),
+        ("DiffChange", Some(yellow), Some(black), None, None),
+        ("DiffDelete", Some(red), Some(black), None, None),
+        ("DiffText", Some(blue), Some(black), Some("bold"), None),
+        // Git signs
+        ("GitSignsAdd", Some(green), None, None, None),
+        ("GitSignsChange", Some(yellow), None, None, None),
+        ("GitSignsDelete", Some(red), None, None, None),
+        // Treesitter highlights
+        ("TSAnnotation", Some(yellow), None, None, None),
+        ("TSAttribute", Some(cyan), None, None, None),
+        ("TSBoolean", Some(magenta), None, None, None),
+        ("TSCharacter", Some(green), None, None, None),
+        ("TSComment", Some(bright_black), None, Some("italic"), None),
+        ("TSConditional", Some(red), None, None, None),
+        ("TSConstant", Some(magenta), None, None, None),
+        ("TSConstBuiltin", Some(magenta), None, None, None),
+        ("TSConstMacro", Some(magenta), None, None, None),
+        ("TSConstructor", Some(blue), None, None, None),
+        ("TSError", Some(bright_red), None, None, None),
+        ("TSException", Some(red), None, None, None),
+        ("TSField", Some(cyan), None, None, None),
+        ("TSFloat", Some(magenta), None, None, None),
+        ("TSFunction", Some(blue), None, None, None),
+        ("TSFuncBuiltin", Some(blue), None, None, None),
+        ("TSFuncMacro", Some(blue), None, None, None),
+        ("TSInclude", Some(blue), None, None, None),
+        ("TSKeyword", Some(red), None, None, None),
+        ("TSKeywordFunction", Some(red), None, None, None),
+        ("TSKeywordOperator", Some(red), None, None, None),
+        ("TSLabel", Some(red), None, None, None),
+        ("TSMethod", Some(blue), None, None, None),
+        ("TSNamespace", Some(yellow), None, None, None),
+        ("TSNumber", Some(magenta), None, None, None),
+        ("TSOperator", Some(foreground), None, None, None),
+        ("TSParameter", Some(cyan), None, None, None),
+        ("TSParameterReference", Some(cyan), None, None, None),
+        ("TSProperty", Some(cyan), None, None, None),
+        ("TSPunctDelimiter", Some(foreground), None, None, None),
+        ("TSPunctBracket", Some(foreground), None, None, None),
+        ("TSPunctSpecial", Some(cyan), None, None, None),
+        ("TSRepeat", Some(red), None, None, None),
+        ("TSString", Some(green), None, None, None),
+        ("TSStringRegex", Some(green), None, None, None),
+        ("TSStringEscape", Some(cyan), None, None, None),
+        ("TSSymbol", Some(cyan), None, None, None),
+        ("TSTag", Some(red), None, None, None),
+        ("TSTagDelimiter", Some(foreground), None, None, None),
+        ("TSText", Some(foreground), None, None, None),
+        ("TSStrong", None, None, Some("bold"), None),
+        ("TSEmphasis", None, None, Some("italic"), None),
+        ("TSUnderline", None, None, Some("underline"), None),
+        ("TSStrike", None, None, Some("strikethrough"), None),
+        ("TSTitle", Some(blue), None, Some("bold"), None),
+        ("TSLiteral", Some(green), None, None, None),
+        ("TSURI", Some(blue), None, Some("underline"), None),
+        ("TSType", Some(yellow), None, None, None),
+        ("TSTypeBuiltin", Some(yellow), None, None, None),
+        ("TSVariable", Some(foreground), None, None, None),
+        ("TSVariableBuiltin", Some(cyan), None, None, None),
+        // LSP highlights
+        ("DiagnosticError", Some(red), None, None, None),
+        ("DiagnosticWarn", Some(yellow), None, None, None),
+        ("DiagnosticInfo", Some(blue), None, None, None),
+        ("DiagnosticHint", Some(cyan), None, None, None),
+        ("DiagnosticUnderlineError", None, None, Some("underline"), Some(red)),
+        ("DiagnosticUnderlineWarn", None, None, Some("underline"), Some(yellow)),
+        ("DiagnosticUnderlineInfo", None, None, Some("underline"), Some(blue)),
+        ("DiagnosticUnderlineHint", None, None, Some("underline"), Some(cyan)),
+    ];
+
+    let variant = if relative_luminance(background) < 0.5 { "dark" } else { "light" };
+    let mut content = format!(
+        "\" Vim colorscheme - Generated by themey
+\" This file is auto-generated. Do not edit manually.
+
+hi clear
+if exists('syntax_on')
+  syntax reset
+endif
+
+set background={}
+let g:colors_name = 'themey'
+
+",
+        variant
+    );
+
+    for (group, fg, bg, style, sp) in groups {
+        let mut line = format!("hi {}", group);
+        if let Some(fg) = fg {
+            line.push_str(&format!(" guifg={} ctermfg={}", fg, hex_to_xterm256(fg)));
+        }
+        if let Some(bg) = bg {
+            line.push_str(&format!(" guibg={} ctermbg={}", bg, hex_to_xterm256(bg)));
+        }
+        if let Some(style) = style {
+            line.push_str(&format!(" gui={} cterm={}", style, style));
+        }
+        if let Some(sp) = sp {
+            line.push_str(&format!(" guisp={}", sp));
+        }
+        content.push_str(&line);
+        content.push('\n');
+    }
+
+    // Terminal colors
+    let terminal = [
+        &colors.normal.black, &colors.normal.red, &colors.normal.green, &colors.normal.yellow,
+        &colors.normal.blue, &colors.normal.magenta, &colors.normal.cyan, &colors.normal.white,
+        &colors.bright.black, &colors.bright.red, &colors.bright.green, &colors.bright.yellow,
+        &colors.bright.blue, &colors.bright.magenta, &colors.bright.cyan, &colors.bright.white,
+    ];
+    let quoted: Vec<String> = terminal.iter().map(|c| format!("'{}'", c)).collect();
+    content.push_str(&format!("\nlet g:terminal_ansi_colors = [{}]\n", quoted.join(", ")));
+
+    // Like neovim, running instances pick this up on the next :colorscheme themey
+    (path, content, None)
+}
+
+fn generate_vscode_config(colors: &Colors, home: &str) -> TargetOutput {
+    let ui_theme = if relative_luminance(&colors.special.background) < 0.5 { "vs-dark" } else { "vs" };
+    let package_json = format!(
+        "{{
+  \"name\": \"themey-theme\",
+  \"displayName\": \"Themey\",
+  \"description\": \"Color theme generated by themey\",
+  \"publisher\": \"themey\",
+  \"version\": \"1.0.0\",
+  \"engines\": {{ \"vscode\": \"^1.60.0\" }},
+  \"categories\": [\"Themes\"],
+  \"contributes\": {{
+    \"themes\": [
+      {{
+        \"label\": \"Themey\",
+        \"uiTheme\": \"{ui_theme}\",
+        \"path\": \"./themes/themey-color-theme.json\"
+      }}
+    ]
+  }}
+}}
+",
+        ui_theme = ui_theme,
+    );
+
+    let content = format!(
+        "{{
+  \"name\": \"Themey\",
+  \"type\": \"{kind}\",
+  \"colors\": {{
+    \"focusBorder\": \"{blue}\",
+    \"foreground\": \"{foreground}\",
+    \"selection.background\": \"{bright_black}\",
+
+    \"editor.background\": \"{background}\",
+    \"editor.foreground\": \"{foreground}\",
+    \"editorCursor.foreground\": \"{cursor}\",
+    \"editor.selectionBackground\": \"{bright_black}\",
+    \"editor.lineHighlightBackground\": \"{black}\",
+    \"editor.findMatchBackground\": \"{yellow}80\",
+    \"editorLineNumber.foreground\": \"{bright_black}\",
+    \"editorLineNumber.activeForeground\": \"{yellow}\",
+    \"editorBracketMatch.border\": \"{bright_cyan}\",
+    \"editorError.foreground\": \"{red}\",
+    \"editorWarning.foreground\": \"{yellow}\",
+    \"editorInfo.foreground\": \"{blue}\",
+    \"editorGutter.addedBackground\": \"{green}\",
+    \"editorGutter.modifiedBackground\": \"{yellow}\",
+    \"editorGutter.deletedBackground\": \"{red}\",
+    \"editorWidget.background\": \"{black}\",
+    \"editorSuggestWidget.selectedBackground\": \"{blue}\",
+    \"editorSuggestWidget.selectedForeground\": \"{background}\",
+
+    \"activityBar.background\": \"{background}\",
+    \"activityBar.foreground\": \"{foreground}\",
+    \"activityBarBadge.background\": \"{blue}\",
+    \"activityBarBadge.foreground\": \"{background}\",
+    \"sideBar.background\": \"{background}\",
+    \"sideBar.foreground\": \"{foreground}\",
+    \"sideBarSectionHeader.background\": \"{black}\",
+    \"list.activeSelectionBackground\": \"{blue}\",
+    \"list.activeSelectionForeground\": \"{background}\",
+    \"list.hoverBackground\": \"{black}\",
+    \"list.inactiveSelectionBackground\": \"{bright_black}\",
+
+    \"titleBar.activeBackground\": \"{background}\",
+    \"titleBar.activeForeground\": \"{foreground}\",
+    \"titleBar.inactiveBackground\": \"{background}\",
+    \"titleBar.inactiveForeground\": \"{bright_black}\",
+    \"statusBar.background\": \"{black}\",
+    \"statusBar.foreground\": \"{foreground}\",
+    \"statusBar.noFolderBackground\": \"{black}\",
+    \"statusBar.debuggingBackground\": \"{red}\",
+
+    \"tab.activeBackground\": \"{background}\",
+    \"tab.activeForeground\": \"{foreground}\",
+    \"tab.inactiveBackground\": \"{black}\",
+    \"tab.inactiveForeground\": \"{bright_black}\",
+    \"editorGroupHeader.tabsBackground\": \"{black}\",
+
+    \"panel.background\": \"{background}\",
+    \"panel.border\": \"{bright_black}\",
+    \"input.background\": \"{black}\",
+    \"input.foreground\": \"{foreground}\",
+    \"dropdown.background\": \"{black}\",
+    \"button.background\": \"{blue}\",
+    \"button.foreground\": \"{background}\",
+
+    \"terminal.background\": \"{background}\",
+    \"terminal.foreground\": \"{foreground}\",
+    \"terminalCursor.foreground\": \"{cursor}\",
+    \"terminal.ansiBlack\": \"{black}\",
+    \"terminal.ansiRed\": \"{red}\",
+    \"terminal.ansiGreen\": \"{green}\",
+    \"terminal.ansiYellow\": \"{yellow}\",
+    \"terminal.ansiBlue\": \"{blue}\",
+    \"terminal.ansiMagenta\": \"{magenta}\",
+    \"terminal.ansiCyan\": \"{cyan}\",
+    \"terminal.ansiWhite\": \"{white}\",
+    \"terminal.ansiBrightBlack\": \"{bright_black}\",
+    \"terminal.ansiBrightRed\": \"{bright_red}\",
+    \"terminal.ansiBrightGreen\": \"{bright_green}\",
+    \"terminal.ansiBrightYellow\": \"{bright_yellow}\",
+    \"terminal.ansiBrightBlue\": \"{bright_blue}\",
+    \"terminal.ansiBrightMagenta\": \"{bright_magenta}\",
+    \"terminal.ansiBrightCyan\": \"{bright_cyan}\",
+    \"terminal.ansiBrightWhite\": \"{bright_white}\"
+  }},
+  \"tokenColors\": [
+    {{ \"scope\": [\"comment\", \"punctuation.definition.comment\"], \"settings\": {{ \"foreground\": \"{bright_black}\", \"fontStyle\": \"italic\" }} }},
+    {{ \"scope\": [\"string\", \"string.quoted\"], \"settings\": {{ \"foreground\": \"{green}\" }} }},
+    {{ \"scope\": [\"constant.character.escape\", \"string.regexp\"], \"settings\": {{ \"foreground\": \"{cyan}\" }} }},
+    {{ \"scope\": [\"constant\", \"constant.numeric\", \"constant.language\"], \"settings\": {{ \"foreground\": \"{magenta}\" }} }},
+    {{ \"scope\": [\"keyword\", \"keyword.control\", \"storage.modifier\"], \"settings\": {{ \"foreground\": \"{red}\" }} }},
+    {{ \"scope\": [\"keyword.operator\", \"punctuation\"], \"settings\": {{ \"foreground\": \"{foreground}\" }} }},
+    {{ \"scope\": [\"storage.type\", \"entity.name.type\", \"support.type\", \"entity.name.namespace\"], \"settings\": {{ \"foreground\": \"{yellow}\" }} }},
+    {{ \"scope\": [\"meta.preprocessor\", \"keyword.control.directive\", \"entity.name.function.macro\"], \"settings\": {{ \"foreground\": \"{yellow}\" }} }},
+    {{ \"scope\": [\"entity.name.function\", \"support.function\", \"meta.function-call\"], \"settings\": {{ \"foreground\": \"{blue}\" }} }},
+    {{ \"scope\": [\"keyword.control.import\", \"keyword.control.include\"], \"settings\": {{ \"foreground\": \"{blue}\" }} }},
+    {{ \"scope\": [\"variable\"], \"settings\": {{ \"foreground\": \"{foreground}\" }} }},
+    {{ \"scope\": [\"variable.parameter\", \"variable.other.member\", \"variable.language\", \"entity.other.attribute-name\"], \"settings\": {{ \"foreground\": \"{cyan}\" }} }},
+    {{ \"scope\": [\"entity.name.tag\"], \"settings\": {{ \"foreground\": \"{red}\" }} }},
+    {{ \"scope\": [\"markup.heading\"], \"settings\": {{ \"foreground\": \"{blue}\", \"fontStyle\": \"bold\" }} }},
+    {{ \"scope\": [\"markup.bold\"], \"settings\": {{ \"fontStyle\": \"bold\" }} }},
+    {{ \"scope\": [\"markup.italic\"], \"settings\": {{ \"fontStyle\": \"italic\" }} }},
+    {{ \"scope\": [\"markup.underline.link\"], \"settings\": {{ \"foreground\": \"{blue}\", \"fontStyle\": \"underline\" }} }},
+    {{ \"scope\": [\"markup.inserted\"], \"settings\": {{ \"foreground\": \"{green}\" }} }},
+    {{ \"scope\": [\"markup.changed\"], \"settings\": {{ \"foreground\": \"{yellow}\" }} }},
+    {{ \"scope\": [\"markup.deleted\"], \"settings\": {{ \"foreground\": \"{red}\" }} }},
+    {{ \"scope\": [\"invalid\"], \"settings\": {{ \"foreground\": \"{bright_red}\" }} }}
+  ]
+}}
+",
+        kind = if ui_theme == "vs" { "light" } else { "dark" },
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        cursor = colors.special.cursor,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+        white = colors.normal.white,
+        bright_black = colors.bright.black,
+        bright_red = colors.bright.red,
+        bright_green = colors.bright.green,
+        bright_yellow = colors.bright.yellow,
+        bright_blue = colors.bright.blue,
+        bright_magenta = colors.bright.magenta,
+        bright_cyan = colors.bright.cyan,
+        bright_white = colors.bright.white,
+    );
+
+    // Install the extension into VS Code and VSCodium, whichever are installed
+    let mut files = Vec::new();
+    let mut notes = Vec::new();
+    for root in [".vscode", ".vscode-oss"] {
+        let extensions = format!("{}/{}/extensions", home, root);
+        if !std::path::Path::new(&extensions).is_dir() {
+            continue;
+        }
+        let dir = format!("{}/themey-theme", extensions);
+        files.push((format!("{}/package.json", dir), package_json.clone()));
+        files.push((format!("{}/themes/themey-color-theme.json", dir), content.clone()));
+
+        // Recent versions only load extensions listed in extensions.json;
+        // without one the extensions directory is scanned
+        let index = format!("{}/extensions.json", extensions);
+        if let Ok(json) = std::fs::read_to_string(&index) {
+            match register_vscode_extension(&json, &dir) {
+                Some(Some(updated)) => files.push((index, updated)),
+                Some(None) => {}
+                None => notes.push(format!(
+                    "  ⚠ Could not read {}; install the extension from {} if it doesn't show up",
+                    index, dir
+                )),
+            }
+        }
+    }
+    if files.is_empty() {
+        notes.push("  ⚠ No VS Code or VSCodium extensions directory found".to_string());
+    }
+
+    // VS Code picks up theme changes on the next window reload
+    TargetOutput { files, reload: None, notes }
+}
+
+// Add the themey extension to an extensions.json index. None if the index
+// can't be parsed, Some(None) if the extension is already listed
+fn register_vscode_extension(json: &str, dir: &str) -> Option<Option<String>> {
+    let id = "themey.themey-theme";
+    let mut index: serde_json::Value = serde_json::from_str(json).ok()?;
+    let entries = index.as_array_mut()?;
+    if entries.iter().any(|e| e["identifier"]["id"].as_str() == Some(id)) {
+        return Some(None);
+    }
+
+    entries.push(serde_json::json!({
+        "identifier": { "id": id },
+        "version": "1.0.0",
+        "location": { "$mid": 1, "fsPath": dir, "path": dir, "scheme": "file" },
+        "relativeLocation": "themey-theme",
+    }));
+    serde_json::to_string(&index).ok().map(Some)
+}
+
+fn generate_emacs_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    // The theme directory can be pointed at any custom-theme-load-path entry
+    let dir = std::env::var("THEMEY_EMACS_DIR")
+        .unwrap_or_else(|_| format!("{}/.emacs.d/themes", home));
+    let path = format!("{}/themey-theme.el", dir);
+    let content = format!(
+        ";;; themey-theme.el --- Emacs theme generated by themey -*- lexical-binding: t -*-
+;;; Commentary:
+;; This file is auto-generated. Do not edit manually.
+;; Load it with:
+;;   (add-to-list 'custom-theme-load-path \"{dir}\")
+;;   (load-theme 'themey t)
+;;; Code:
+
+(deftheme themey \"Theme generated by themey.\")
+
+(let ((background \"{background}\")
+      (foreground \"{foreground}\")
+      (cursor \"{cursor}\")
+      (black \"{black}\")
+      (red \"{red}\")
+      (green \"{green}\")
+      (yellow \"{yellow}\")
+      (blue \"{blue}\")
+      (magenta \"{magenta}\")
+      (cyan \"{cyan}\")
+      (white \"{white}\")
+      (bright-black \"{bright_black}\")
+      (bright-red \"{bright_red}\")
+      (bright-yellow \"{bright_yellow}\")
+      (bright-cyan \"{bright_cyan}\"))
+  (custom-theme-set-faces
+   'themey
+   ;; Editor
+   `(default ((t (:foreground ,foreground :background ,background))))
+   `(cursor ((t (:background ,cursor))))
+   `(region ((t (:background ,bright-black))))
+   `(hl-line ((t (:background ,black))))
+   `(fringe ((t (:background ,background))))
+   `(vertical-border ((t (:foreground ,bright-black))))
+   `(line-number ((t (:foreground ,bright-black))))
+   `(line-number-current-line ((t (:foreground ,yellow :weight bold))))
+   `(minibuffer-prompt ((t (:foreground ,blue :weight bold))))
+   `(isearch ((t (:foreground ,background :background ,bright-yellow))))
+   `(lazy-highlight ((t (:foreground ,background :background ,yellow))))
+   `(show-paren-match ((t (:foreground ,bright-cyan :weight bold))))
+   `(link ((t (:foreground ,blue :underline t))))
+   `(error ((t (:foreground ,red))))
+   `(warning ((t (:foreground ,yellow))))
+   `(success ((t (:foreground ,green))))
+
+   ;; Mode line
+   `(mode-line ((t (:foreground ,foreground :background ,black))))
+   `(mode-line-inactive ((t (:foreground ,bright-black :background ,black))))
+
+   ;; Syntax highlighting
+   `(font-lock-comment-face ((t (:foreground ,bright-black :slant italic))))
+   `(font-lock-doc-face ((t (:foreground ,bright-black :slant italic))))
+   `(font-lock-string-face ((t (:foreground ,green))))
+   `(font-lock-constant-face ((t (:foreground ,magenta))))
+   `(font-lock-number-face ((t (:foreground ,magenta))))
+   `(font-lock-keyword-face ((t (:foreground ,red))))
+   `(font-lock-builtin-face ((t (:foreground ,cyan))))
+   `(font-lock-function-name-face ((t (:foreground ,blue))))
+   `(font-lock-variable-name-face ((t (:foreground ,cyan))))
+   `(font-lock-type-face ((t (:foreground ,yellow))))
+   `(font-lock-preprocessor-face ((t (:foreground ,yellow))))
+   `(font-lock-negation-char-face ((t (:foreground ,red))))
+   `(font-lock-warning-face ((t (:foreground ,bright-red :weight bold))))
+
+   ;; Diff
+   `(diff-added ((t (:foreground ,green :background ,black))))
+   `(diff-removed ((t (:foreground ,red :background ,black))))
+   `(diff-changed ((t (:foreground ,yellow :background ,black))))
+   `(diff-refine-added ((t (:foreground ,background :background ,green))))
+   `(diff-refine-removed ((t (:foreground ,background :background ,red))))
+   `(diff-header ((t (:foreground ,blue :background ,black))))
+   `(diff-file-header ((t (:foreground ,blue :background ,black :weight bold))))
+   `(diff-hunk-header ((t (:foreground ,cyan :background ,black))))
+
+   ;; Org
+   `(org-level-1 ((t (:foreground ,blue :weight bold :height 1.2))))
+   `(org-level-2 ((t (:foreground ,magenta :weight bold :height 1.1))))
+   `(org-level-3 ((t (:foreground ,cyan :weight bold))))
+   `(org-level-4 ((t (:foreground ,yellow :weight bold))))
+   `(org-level-5 ((t (:foreground ,green))))
+   `(org-level-6 ((t (:foreground ,red))))
+   `(org-block ((t (:background ,black))))
+   `(org-code ((t (:foreground ,green))))
+   `(org-todo ((t (:foreground ,red :weight bold))))
+   `(org-done ((t (:foreground ,green :weight bold))))
+   `(org-link ((t (:foreground ,blue :underline t)))))
+
+  (custom-theme-set-variables
+   'themey
+   `(ansi-color-names-vector [,black ,red ,green ,yellow ,blue ,magenta ,cyan ,white])))
+
+(provide-theme 'themey)
+;;; themey-theme.el ends here
+",
+        dir = dir,
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        cursor = colors.special.cursor,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+        white = colors.normal.white,
+        bright_black = colors.bright.black,
+        bright_red = colors.bright.red,
+        bright_yellow = colors.bright.yellow,
+        bright_cyan = colors.bright.cyan,
+    );
+
+    // Reload the theme in a running Emacs daemon, if any
+    let reload = Some((
+        "emacsclient".to_string(),
+        vec![
+            "-e".to_string(),
+            format!(
+                "(progn (add-to-list 'custom-theme-load-path \"{}\") (load-theme 'themey t))",
+                dir
+            ),
+        ],
+        "emacs".to_string(),
+    ));
+
+    (path, content, reload)
+}
+
+// Drop the tried theme from a running daemon, loading the restored file if
+// there was one before
+fn emacs_revert(path: &str) -> ReloadCmd {
+    Some((
+        "emacsclient".to_string(),
+        vec![
+            "-e".to_string(),
+            format!(
+                "(progn (disable-theme 'themey) (when (file-exists-p \"{}\") (load-theme 'themey t)))",
+                path
+            ),
+        ],
+        "emacs".to_string(),
+    ))
+}
+
+fn generate_bat_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/bat/themes/themey.tmTheme", home);
+
+    let foreground = colors.special.foreground.as_str();
+    let black = colors.normal.black.as_str();
+    let red = colors.normal.red.as_str();
+    let green = colors.normal.green.as_str();
+    let yellow = colors.normal.yellow.as_str();
+    let blue = colors.normal.blue.as_str();
+    let magenta = colors.normal.magenta.as_str();
+    let cyan = colors.normal.cyan.as_str();
+    let bright_black = colors.bright.black.as_str();
+    let bright_red = colors.bright.red.as_str();
+
+    // TextMate scopes mapped like the neovim highlight groups: (name, scope, fg, style)
+    let scopes: &[(&str, &str, &str, &str)] = &[
+        ("Comment", "comment, punctuation.definition.comment", bright_black, "italic"),
+        ("String", "string", green, ""),
+        ("Escape", "constant.character.escape, string.regexp", cyan, ""),
+        ("Constant", "constant, constant.numeric, constant.language", magenta, ""),
+        ("Keyword", "keyword, keyword.control, storage.modifier", red, ""),
+        ("Operator", "keyword.operator, punctuation", foreground, ""),
+        ("Include", "keyword.control.import, keyword.control.include", blue, ""),
+        ("PreProc", "meta.preprocessor, entity.name.function.macro", yellow, ""),
+        ("Type", "storage.type, entity.name.type, support.type, entity.name.namespace", yellow, ""),
+        ("Function", "entity.name.function, support.function, meta.function-call", blue, ""),
+        ("Variable", "variable", foreground, ""),
+        ("Identifier", "variable.parameter, variable.other.member, variable.language", cyan, ""),
+        ("Attribute", "entity.other.attribute-name", cyan, ""),
+        ("Tag", "entity.name.tag", red, ""),
+        ("Heading", "markup.heading", blue, "bold"),
+        ("Bold", "markup.bold", foreground, "bold"),
+        ("Italic", "markup.italic", foreground, "italic"),
+        ("Link", "markup.underline.link", blue, "underline"),
+        ("Inserted", "markup.inserted", green, ""),
+        ("Deleted", "markup.deleted", red, ""),
+        ("Changed", "markup.changed", yellow, ""),
+        ("Diff header", "meta.diff.header, meta.diff.range", blue, ""),
+        ("Invalid", "invalid", bright_red, ""),
+    ];
+
+    let mut content = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<!-- bat / delta theme - Generated by themey -->
+<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">
+<plist version=\"1.0\">
+<dict>
+  <key>name</key>
+  <string>Themey</string>
+  <key>settings</key>
+  <array>
+    <dict>
+      <key>settings</key>
+      <dict>
+        <key>background</key>
+        <string>{background}</string>
+        <key>foreground</key>
+        <string>{foreground}</string>
+        <key>caret</key>
+        <string>{cursor}</string>
+        <key>lineHighlight</key>
+        <string>{black}</string>
+        <key>selection</key>
+        <string>{bright_black}</string>
+        <key>gutter</key>
+        <string>{background}</string>
+        <key>gutterForeground</key>
+        <string>{bright_black}</string>
+        <key>invisibles</key>
+        <string>{bright_black}</string>
+        <key>findHighlight</key>
+        <string>{yellow}</string>
+      </dict>
+    </dict>
+",
+        background = colors.special.background,
+        foreground = foreground,
+        cursor = colors.special.cursor,
+        black = black,
+        bright_black = bright_black,
+        yellow = yellow,
+    );
+
+    for (name, scope, fg, style) in scopes {
+        content.push_str(&format!(
+            "    <dict>
+      <key>name</key>
+      <string>{}</string>
+      <key>scope</key>
+      <string>{}</string>
+      <key>settings</key>
+      <dict>
+        <key>foreground</key>
+        <string>{}</string>
+        <key>fontStyle</key>
+        <string>{}</string>
+      </dict>
+    </dict>
+",
+            name, scope, fg, style
+        ));
+    }
+
+    content.push_str("  </array>\n</dict>\n</plist>\n");
+
+    // bat (and delta, which reads bat's cache) only sees new themes after a rebuild
+    let reload = Some((
+        "bat".to_string(),
+        vec!["cache".to_string(), "--build".to_string()],
+        "bat".to_string(),
+    ));
+
+    (path, content, reload)
+}
+
+fn generate_dircolors_config(colors: &Colors, home: &str) -> TargetOutput {
+    let path = format!("{}/.config/themey/dircolors", home);
+
+    let archive = truecolor(&colors.normal.red);
+    let media = truecolor(&colors.normal.magenta);
+    let archives = [
+        ".tar", ".tgz", ".gz", ".xz", ".zst", ".bz2", ".lz", ".zip", ".7z", ".rar", ".deb", ".rpm",
+    ];
+    let media_exts = [
+        ".png", ".jpg", ".jpeg", ".gif", ".webp", ".bmp", ".svg", ".mp3", ".flac", ".ogg", ".wav",
+        ".mp4", ".mkv", ".webm", ".mov", ".avi",
+    ];
+
+    let mut content = format!(
+        "# dircolors database - Generated by themey
+# Load it with: eval \"$(dircolors -b ~/.config/themey/dircolors)\"
+# or source ~/.config/themey/dircolors.sh
+
+RESET 0
+DIR 01;{dir}
+LINK {link}
+ORPHAN 01;{orphan}
+MISSING 01;{orphan}
+EXEC 01;{exec}
+FIFO {fifo}
+SOCK 01;{sock}
+BLK 01;{device}
+CHR 01;{device}
+SETUID {setuid}
+SETGID {setuid}
+STICKY_OTHER_WRITABLE {sticky}
+OTHER_WRITABLE {sticky}
+STICKY {sticky}
+
+# Archives
+",
+        dir = truecolor(&colors.normal.blue),
+        link = truecolor(&colors.normal.cyan),
+        orphan = truecolor(&colors.bright.red),
+        exec = truecolor(&colors.normal.green),
+        fifo = truecolor(&colors.normal.yellow),
+        sock = truecolor(&colors.normal.magenta),
+        device = truecolor(&colors.bright.yellow),
+        setuid = truecolor(&colors.bright.red),
+        sticky = truecolor(&colors.bright.blue),
+    );
+    for ext in archives {
+        content.push_str(&format!("{} {}\n", ext, archive));
+    }
+    content.push_str("\n# Media\n");
+    for ext in media_exts {
+        content.push_str(&format!("{} {}\n", ext, media));
+    }
+
+    // Shell snippet to eval from .bashrc/.zshrc
+    let snippet = format!(
+        "# Generated by themey - source this from your shell rc file
+eval \"$(dircolors -b '{}')\"
+",
+        path
+    );
+    let mut files = vec![
+        (path, content),
+        (format!("{}/.config/themey/dircolors.sh", home), snippet),
+    ];
+    let mut notes = Vec::new();
+
+    // eza reads its own theme file instead of LS_COLORS
+    let eza_theme = format!(
+        "# eza theme - Generated by themey
+filekinds:
+  normal: {{ foreground: \"{foreground}\" }}
+  directory: {{ foreground: \"{blue}\", is_bold: true }}
+  symlink: {{ foreground: \"{cyan}\" }}
+  pipe: {{ foreground: \"{yellow}\" }}
+  block_device: {{ foreground: \"{bright_yellow}\", is_bold: true }}
+  char_device: {{ foreground: \"{bright_yellow}\", is_bold: true }}
+  socket: {{ foreground: \"{magenta}\", is_bold: true }}
+  special: {{ foreground: \"{yellow}\" }}
+  executable: {{ foreground: \"{green}\", is_bold: true }}
+  mount_point: {{ foreground: \"{blue}\", is_underline: true }}
+
+file_type:
+  image: {{ foreground: \"{magenta}\" }}
+  video: {{ foreground: \"{magenta}\" }}
+  music: {{ foreground: \"{magenta}\" }}
+  lossless: {{ foreground: \"{magenta}\" }}
+  compressed: {{ foreground: \"{red}\" }}
+  crypto: {{ foreground: \"{bright_red}\" }}
+  document: {{ foreground: \"{foreground}\" }}
+  temp: {{ foreground: \"{bright_black}\" }}
+  compiled: {{ foreground: \"{bright_black}\" }}
+  build: {{ foreground: \"{yellow}\", is_underline: true }}
+  source: {{ foreground: \"{foreground}\" }}
+
+git:
+  new: {{ foreground: \"{green}\" }}
+  modified: {{ foreground: \"{yellow}\" }}
+  deleted: {{ foreground: \"{red}\" }}
+  renamed: {{ foreground: \"{cyan}\" }}
+  typechange: {{ foreground: \"{magenta}\" }}
+  ignored: {{ foreground: \"{bright_black}\" }}
+  conflicted: {{ foreground: \"{bright_red}\" }}
+
+punctuation: {{ foreground: \"{bright_black}\" }}
+date: {{ foreground: \"{blue}\" }}
+header: {{ foreground: \"{foreground}\", is_underline: true }}
+symlink_path: {{ foreground: \"{cyan}\" }}
+broken_symlink: {{ foreground: \"{bright_red}\" }}
+",
+        foreground = colors.special.foreground,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+        bright_black = colors.bright.black,
+        bright_red = colors.bright.red,
+        bright_yellow = colors.bright.yellow,
+    );
+
+    // Leave a theme.yml the user wrote themselves alone
+    let eza_path = format!("{}/.config/eza/theme.yml", home);
+    match std::fs::read_to_string(&eza_path) {
+        Ok(existing) if !existing.starts_with("# eza theme - Generated by themey") => {
+            notes.push(format!("  ⚠ Skipped {} (not generated by themey)", eza_path));
+        }
+        _ => files.push((eza_path, eza_theme)),
+    }
+
+    // New shells pick up LS_COLORS from the snippet
+    TargetOutput { files, reload: None, notes }
+}
+
+fn generate_fzf_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/themey/fzf.sh", home);
+    let content = format!(
+        "# fzf colors - Generated by themey
+# Source this from your shell rc file: . ~/.config/themey/fzf.sh
+export FZF_DEFAULT_OPTS=\"$FZF_DEFAULT_OPTS \\
+  --color=fg:{foreground},bg:{background},hl:{blue} \\
+  --color=fg+:{foreground},bg+:{black},hl+:{bright_blue} \\
+  --color=info:{yellow},prompt:{cyan},pointer:{magenta} \\
+  --color=marker:{green},spinner:{magenta},header:{bright_black} \\
+  --color=border:{bright_black},gutter:{background}\"
+",
+        foreground = colors.special.foreground,
+        background = colors.special.background,
+        black = colors.normal.black,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+        bright_black = colors.bright.black,
+        bright_blue = colors.bright.blue,
+    );
+
+    // New shells pick up the exported options
+    (path, content, None)
+}
+
+fn generate_starship_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/starship.toml", home);
+    let start = "# themey palette start";
+    let end = "# themey palette end";
+
+    let block = format!(
+        "{start}
+[palettes.themey]
+background = \"{background}\"
+foreground = \"{foreground}\"
+black = \"{black}\"
+red = \"{red}\"
+green = \"{green}\"
+yellow = \"{yellow}\"
+blue = \"{blue}\"
+purple = \"{magenta}\"
+magenta = \"{magenta}\"
+cyan = \"{cyan}\"
+white = \"{white}\"
+bright-black = \"{bright_black}\"
+bright-red = \"{bright_red}\"
+bright-green = \"{bright_green}\"
+bright-yellow = \"{bright_yellow}\"
+bright-blue = \"{bright_blue}\"
+bright-purple = \"{bright_magenta}\"
+bright-magenta = \"{bright_magenta}\"
+bright-cyan = \"{bright_cyan}\"
+bright-white = \"{bright_white}\"
+{end}
+",
+        start = start,
+        end = end,
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+        white = colors.normal.white,
+        bright_black = colors.bright.black,
+        bright_red = colors.bright.red,
+        bright_green = colors.bright.green,
+        bright_yellow = colors.bright.yellow,
+        bright_blue = colors.bright.blue,
+        bright_magenta = colors.bright.magenta,
+        bright_cyan = colors.bright.cyan,
+        bright_white = colors.bright.white,
+    );
+
+    let existing = std::fs::read_to_string(&path).unwrap_or_default();
+    let content = inject_starship_palette(&existing, &block, start, end);
+
+    // Starship re-reads its config on every prompt
+    (path, content, None)
+}
+
+// Inject the palette into the user's existing config, replacing a previous one
+fn inject_starship_palette(existing: &str, block: &str, start: &str, end: &str) -> String {
+    let mut content = match (existing.find(start), existing.find(end)) {
+        (Some(s), Some(e)) if s < e => {
+            let after = existing[e + end.len()..].trim_start_matches('\n');
+            format!("{}{}{}", &existing[..s], block, after)
+        }
+        _ if existing.is_empty() => block.to_string(),
+        _ => format!("{}\n\n{}", existing.trim_end(), block),
+    };
+
+    // Select the palette unless the user already picked one
+    let has_palette = content.parse::<toml::Table>()
+        .map(|t| t.contains_key("palette"))
+        .unwrap_or(false);
+    if !has_palette {
+        content = format!("palette = \"themey\"\n\n{}", content);
+    }
+    content
+}
+
+fn generate_fish_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/fish/conf.d/themey.fish", home);
+    let content = format!(
+        "# fish colors - Generated by themey
+set -U fish_color_normal {foreground}
+set -U fish_color_command {blue}
+set -U fish_color_keyword {red}
+set -U fish_color_quote {green}
+set -U fish_color_redirection {cyan}
+set -U fish_color_end {magenta}
+set -U fish_color_error {bright_red}
+set -U fish_color_param {foreground}
+set -U fish_color_option {cyan}
+set -U fish_color_valid_path --underline
+set -U fish_color_comment {bright_black}
+set -U fish_color_operator {cyan}
+set -U fish_color_escape {magenta}
+set -U fish_color_autosuggestion {bright_black}
+set -U fish_color_selection --background={bright_black}
+set -U fish_color_search_match --background={bright_black}
+set -U fish_color_history_current --bold
+set -U fish_color_cancel {red}
+set -U fish_color_cwd {green}
+set -U fish_color_cwd_root {red}
+set -U fish_color_user {green}
+set -U fish_color_host {blue}
+set -U fish_color_host_remote {yellow}
+set -U fish_color_status {red}
+
+set -U fish_pager_color_prefix {cyan} --bold
+set -U fish_pager_color_completion {foreground}
+set -U fish_pager_color_description {yellow}
+set -U fish_pager_color_progress {bright_black}
+set -U fish_pager_color_selected_background --background={black}
+",
+        foreground = strip_hash(&colors.special.foreground),
+        black = strip_hash(&colors.normal.black),
+        red = strip_hash(&colors.normal.red),
+        green = strip_hash(&colors.normal.green),
+        yellow = strip_hash(&colors.normal.yellow),
+        blue = strip_hash(&colors.normal.blue),
+        magenta = strip_hash(&colors.normal.magenta),
+        cyan = strip_hash(&colors.normal.cyan),
+        bright_black = strip_hash(&colors.bright.black),
+        bright_red = strip_hash(&colors.bright.red),
+    );
+
+    // Universal variables propagate to every running fish session
+    let reload = Some((
+        "fish".to_string(),
+        vec!["-c".to_string(), format!("source '{}'", path)],
+        "fish".to_string(),
+    ));
+
+    (path, content, reload)
+}
+
+// Universal variables outlive the conf.d file, so record what each one the
+// theme sets was before: a fish script that sets or erases them again
+fn fish_revert(content: &str) -> ReloadCmd {
+    let names: Vec<&str> = content.lines()
+        .filter_map(|l| l.strip_prefix("set -U "))
+        .filter_map(|l| l.split_whitespace().next())
+        .collect();
+    let script = format!(
+        "for v in {}; if set -qU $v; echo set -U $v (string escape -- $$v); else; echo set -Ue $v; end; end",
+        names.join(" ")
+    );
+    let previous = query("fish", &["-c", &script])?;
+
+    Some((
+        "fish".to_string(),
+        vec!["-c".to_string(), previous],
+        "fish".to_string(),
+    ))
+}
+
+fn generate_btop_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/btop/themes/themey.theme", home);
+    let content = format!(
+        "# btop theme - Generated by themey
+# Select it in btop.conf with: color_theme = \"themey\"
+
+theme[main_bg]=\"{background}\"
+theme[main_fg]=\"{foreground}\"
+theme[title]=\"{foreground}\"
+theme[hi_fg]=\"{blue}\"
+theme[selected_bg]=\"{black}\"
+theme[selected_fg]=\"{blue}\"
+theme[inactive_fg]=\"{bright_black}\"
+theme[graph_text]=\"{foreground}\"
+theme[meter_bg]=\"{black}\"
+theme[proc_misc]=\"{cyan}\"
+
+theme[cpu_box]=\"{blue}\"
+theme[mem_box]=\"{green}\"
+theme[net_box]=\"{magenta}\"
+theme[proc_box]=\"{cyan}\"
+theme[div_line]=\"{bright_black}\"
+
+theme[temp_start]=\"{green}\"
+theme[temp_mid]=\"{yellow}\"
+theme[temp_end]=\"{red}\"
+
+theme[cpu_start]=\"{cyan}\"
+theme[cpu_mid]=\"{blue}\"
+theme[cpu_end]=\"{magenta}\"
+
+theme[free_start]=\"{green}\"
+theme[free_mid]=\"{green}\"
+theme[free_end]=\"{bright_green}\"
+
+theme[cached_start]=\"{blue}\"
+theme[cached_mid]=\"{blue}\"
+theme[cached_end]=\"{bright_blue}\"
+
+theme[available_start]=\"{yellow}\"
+theme[available_mid]=\"{yellow}\"
+theme[available_end]=\"{bright_yellow}\"
+
+theme[used_start]=\"{red}\"
+theme[used_mid]=\"{red}\"
+theme[used_end]=\"{bright_red}\"
+
+theme[download_start]=\"{blue}\"
+theme[download_mid]=\"{cyan}\"
+theme[download_end]=\"{bright_cyan}\"
+
+theme[upload_start]=\"{magenta}\"
+theme[upload_mid]=\"{magenta}\"
+theme[upload_end]=\"{bright_magenta}\"
+
+theme[process_start]=\"{cyan}\"
+theme[process_mid]=\"{blue}\"
+theme[process_end]=\"{magenta}\"
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+        bright_black = colors.bright.black,
+        bright_red = colors.bright.red,
+        bright_green = colors.bright.green,
+        bright_yellow = colors.bright.yellow,
+        bright_blue = colors.bright.blue,
+        bright_magenta = colors.bright.magenta,
+        bright_cyan = colors.bright.cyan,
+    );
+
+    // btop reloads its config on SIGUSR2
+    let reload = Some((
+        "pkill".to_string(),
+        vec!["-USR2".to_string(), "-x".to_string(), "btop".to_string()],
+        "btop".to_string(),
+    ));
+
+    (path, content, reload)
+}
+
+fn generate_lazygit_config(colors: &Colors, home: &str) -> TargetOutput {
+    let path = format!("{}/.config/lazygit/config.yml", home);
+    let theme = format!(
+        "# themey theme start
+theme:
+  activeBorderColor:
+    - \"{blue}\"
+    - bold
+  inactiveBorderColor:
+    - \"{bright_black}\"
+  searchingActiveBorderColor:
+    - \"{yellow}\"
+    - bold
+  optionsTextColor:
+    - \"{blue}\"
+  selectedLineBgColor:
+    - \"{black}\"
+  inactiveViewSelectedLineBgColor:
+    - \"{black}\"
+  cherryPickedCommitFgColor:
+    - \"{background}\"
+  cherryPickedCommitBgColor:
+    - \"{cyan}\"
+  markedBaseCommitFgColor:
+    - \"{background}\"
+  markedBaseCommitBgColor:
+    - \"{yellow}\"
+  unstagedChangesColor:
+    - \"{red}\"
+  defaultFgColor:
+    - \"{foreground}\"
+# themey theme end
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        cyan = colors.normal.cyan,
+        bright_black = colors.bright.black,
+    );
+
+    // lazygit reads its config on startup
+    let existing = std::fs::read_to_string(&path).unwrap_or_default();
+    match inject_lazygit_theme(&existing, &theme) {
+        Some(content) => (path, content, None).into(),
+        None => TargetOutput {
+            files: Vec::new(),
+            reload: None,
+            notes: vec![format!("  ⚠ Skipped {} (it sets gui.theme itself or uses an inline gui mapping)", path)],
+        },
+    }
+}
+
+// Put the marked theme block under the top-level `gui:` key of a lazygit
+// config, replacing a previous one. None if the user set gui.theme themselves
+fn inject_lazygit_theme(existing: &str, theme: &str) -> Option<String> {
+    let start = "# themey theme start";
+    let end = "# themey theme end";
+
+    // Drop the previously injected block
+    let config = strip_marked(existing, start, end);
+
+    let lines: Vec<&str> = config.lines().collect();
+    let is_key = |line: &str, key: &str| line.split(" #").next().unwrap_or("").trim_end() == key;
+
+    let Some(gui) = lines.iter().position(|l| l.starts_with("gui:")) else {
+        let indented: String = theme.lines().map(|l| format!("  {}\n", l)).collect();
+        let sep = if config.is_empty() || config.ends_with('\n') { "" } else { "\n" };
+        return Some(format!("{}{}gui:\n{}", config, sep, indented));
+    };
+    if !is_key(lines[gui], "gui:") {
+        // An inline mapping like `gui: {...}` can't take another key
+        return None;
+    }
+
+    // The gui section runs until the next unindented line
+    let section: Vec<&str> = lines[gui + 1..].iter()
+        .take_while(|l| l.trim().is_empty() || l.starts_with([' ', '\t']) || l.starts_with('#'))
+        .copied()
+        .collect();
+    let indent = section.iter()
+        .find(|l| !l.trim().is_empty() && !l.trim_start().starts_with('#'))
+        .map(|l| &l[..l.len() - l.trim_start().len()])
+        .unwrap_or("  ");
+    if section.iter().any(|l| l.strip_prefix(indent).is_some_and(|k| k.starts_with("theme:"))) {
+        return None;
+    }
+
+    let mut out: Vec<String> = lines[..=gui].iter().map(|l| l.to_string()).collect();
+    out.extend(theme.lines().map(|l| format!("{}{}", indent, l)));
+    out.extend(lines[gui + 1..].iter().map(|l| l.to_string()));
+    Some(out.join("\n") + "\n")
+}
+
+fn generate_yazi_config(colors: &Colors, home: &str) -> TargetOutput {
+    let flavor_path = format!("{}/.config/yazi/flavors/themey.yazi/flavor.toml", home);
+    let flavor = format!(
+        "# Yazi flavor - Generated by themey
+
+[mgr]
+cwd = {{ fg = \"{cyan}\" }}
+hovered = {{ fg = \"{background}\", bg = \"{blue}\" }}
+preview_hovered = {{ underline = true }}
+find_keyword = {{ fg = \"{yellow}\", bold = true }}
+find_position = {{ fg = \"{magenta}\", bg = \"reset\", bold = true }}
+marker_copied = {{ fg = \"{green}\", bg = \"{green}\" }}
+marker_cut = {{ fg = \"{red}\", bg = \"{red}\" }}
+marker_marked = {{ fg = \"{cyan}\", bg = \"{cyan}\" }}
+marker_selected = {{ fg = \"{yellow}\", bg = \"{yellow}\" }}
+border_style = {{ fg = \"{bright_black}\" }}
+
+[tabs]
+active = {{ fg = \"{background}\", bg = \"{blue}\", bold = true }}
+inactive = {{ fg = \"{foreground}\", bg = \"{black}\" }}
+
+[mode]
+normal_main = {{ fg = \"{background}\", bg = \"{blue}\", bold = true }}
+normal_alt = {{ fg = \"{blue}\", bg = \"{black}\" }}
+select_main = {{ fg = \"{background}\", bg = \"{magenta}\", bold = true }}
+select_alt = {{ fg = \"{magenta}\", bg = \"{black}\" }}
+unset_main = {{ fg = \"{background}\", bg = \"{red}\", bold = true }}
+unset_alt = {{ fg = \"{red}\", bg = \"{black}\" }}
+
+[status]
+perm_type = {{ fg = \"{blue}\" }}
+perm_read = {{ fg = \"{yellow}\" }}
+perm_write = {{ fg = \"{red}\" }}
+perm_exec = {{ fg = \"{green}\" }}
+perm_sep = {{ fg = \"{bright_black}\" }}
+progress_label = {{ fg = \"{foreground}\", bold = true }}
+progress_normal = {{ fg = \"{blue}\", bg = \"{black}\" }}
+progress_error = {{ fg = \"{red}\", bg = \"{black}\" }}
+
+[input]
+border = {{ fg = \"{blue}\" }}
+title = {{ fg = \"{foreground}\" }}
+value = {{ fg = \"{foreground}\" }}
+selected = {{ bg = \"{bright_black}\" }}
+
+[filetype]
+rules = [
+    {{ mime = \"image/*\", fg = \"{magenta}\" }},
+    {{ mime = \"{{audio,video}}/*\", fg = \"{magenta}\" }},
+    {{ mime = \"application/{{zip,gzip,x-tar,x-bzip2,x-7z-compressed,x-rar,x-xz,zstd}}\", fg = \"{red}\" }},
+    {{ url = \"*\", is = \"exec\", fg = \"{green}\" }},
+    {{ url = \"*\", is = \"orphan\", fg = \"{bright_red}\" }},
+    {{ url = \"*/\", fg = \"{blue}\" }},
+    {{ url = \"*\", fg = \"{foreground}\" }},
+]
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+        bright_black = colors.bright.black,
+        bright_red = colors.bright.red,
+    );
+
+    // The flavor is selected from theme.toml; leave one the user wrote alone
+    let theme_path = format!("{}/.config/yazi/theme.toml", home);
+    let selector = "# Yazi theme - Generated by themey
+
+[flavor]
+dark = \"themey\"
+light = \"themey\"
+";
+    let mut files = vec![(flavor_path, flavor)];
+    let mut notes = Vec::new();
+    match std::fs::read_to_string(&theme_path) {
+        Ok(existing) if !existing.starts_with("# Yazi theme - Generated by themey") => {
+            notes.push(format!(
+                "  ⚠ Left {} as is; select the flavor with [flavor] dark = \"themey\"",
+                theme_path
+            ));
+        }
+        _ => files.push((theme_path, selector.to_string())),
+    }
+
+    // Yazi reads its theme on startup
+    TargetOutput { files, reload: None, notes }
+}
+
+fn generate_zathura_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/zathura/themey", home);
+    let content = format!(
+        "# Zathura colors - Generated by themey
+# Include this in your zathurarc with: include themey
+
+set default-bg \"{background}\"
+set default-fg \"{foreground}\"
+
+set statusbar-bg \"{black}\"
+set statusbar-fg \"{foreground}\"
+set inputbar-bg \"{background}\"
+set inputbar-fg \"{foreground}\"
+
+set notification-bg \"{background}\"
+set notification-fg \"{foreground}\"
+set notification-error-bg \"{background}\"
+set notification-error-fg \"{red}\"
+set notification-warning-bg \"{background}\"
+set notification-warning-fg \"{yellow}\"
+
+set highlight-color \"{yellow}\"
+set highlight-active-color \"{blue}\"
+
+set completion-bg \"{black}\"
+set completion-fg \"{foreground}\"
+set completion-highlight-bg \"{blue}\"
+set completion-highlight-fg \"{background}\"
+
+set index-bg \"{background}\"
+set index-fg \"{foreground}\"
+set index-active-bg \"{blue}\"
+set index-active-fg \"{background}\"
+
+# Recolor pages to match the theme (toggle with Ctrl+r)
+set recolor-lightcolor \"{background}\"
+set recolor-darkcolor \"{foreground}\"
+set recolor \"true\"
+set recolor-keephue \"true\"
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        black = colors.normal.black,
+        red = colors.normal.red,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+    );
+
+    // Zathura reads its config when a document is opened
+    (path, content, None)
+}
+
+fn generate_qt_config(colors: &Colors, home: &str) -> TargetOutput {
+    let mut files = vec![
+        (format!("{}/.config/qt5ct/colors/themey.conf", home), qt_color_scheme(colors, false)),
+        // qt6ct reads the same format, with an extra Accent role on Qt 6.6+
+        (format!("{}/.config/qt6ct/colors/themey.conf", home), qt_color_scheme(colors, true)),
+    ];
+
+    // Only touch Kvantum when it's installed and configured
+    let kvantum_dir = format!("{}/.config/Kvantum", home);
+    if std::path::Path::new(&kvantum_dir).is_dir() {
+        files.push((format!("{}/Themey/Themey.kvconfig", kvantum_dir), kvantum_colors(colors)));
+    }
+
+    // Qt apps read their palette on startup
+    TargetOutput { files, reload: None, notes: Vec::new() }
+}
+
+// qt5ct/qt6ct color scheme, one list of QPalette roles per color group
+fn qt_color_scheme(colors: &Colors, accent: bool) -> String {
+    let bg = &colors.special.background;
+    let fg = &colors.special.foreground;
+    let blue = &colors.normal.blue;
+    let dimmed = mix_hex(fg, bg, 0.5);
+
+    // Roles in QPalette::ColorRole order
+    let group = |text: &str, highlight: &str| {
+        let mut roles = vec![
+            text.to_string(),              // WindowText
+            mix_hex(bg, fg, 0.1),          // Button
+            mix_hex(bg, fg, 0.3),          // Light
+            mix_hex(bg, fg, 0.2),          // Midlight
+            mix_hex(bg, "#000000", 0.4),   // Dark
+            mix_hex(bg, fg, 0.15),         // Mid
+            text.to_string(),              // Text
+            colors.bright.white.clone(),   // BrightText
+            text.to_string(),              // ButtonText
+            bg.clone(),                    // Base
+            bg.clone(),                    // Window
+            "#000000".to_string(),         // Shadow
+            highlight.to_string(),         // Highlight
+            bg.clone(),                    // HighlightedText
+            blue.clone(),                  // Link
+            colors.normal.magenta.clone(), // LinkVisited
+            mix_hex(bg, fg, 0.05),         // AlternateBase
+            bg.clone(),                    // NoRole
+            colors.normal.black.clone(),   // ToolTipBase
+            fg.clone(),                    // ToolTipText
+            dimmed.clone(),                // PlaceholderText
+        ];
+        if accent {
+            roles.push(highlight.to_string()); // Accent
+        }
+        roles.iter()
+            .map(|c| format!("#ff{}", strip_hash(c)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    format!(
+        "; Qt color scheme - Generated by themey
+; Select it in qt5ct/qt6ct under Palette > Custom > themey
+
+[ColorScheme]
+active_colors={active}
+inactive_colors={inactive}
+disabled_colors={disabled}
+",
+        active = group(fg, blue),
+        inactive = group(fg, &mix_hex(blue, bg, 0.4)),
+        disabled = group(&dimmed, &mix_hex(bg, fg, 0.2)),
+    )
+}
+
+fn kvantum_colors(colors: &Colors) -> String {
+    let bg = &colors.special.background;
+    let fg = &colors.special.foreground;
+    format!(
+        "; Kvantum color override - Generated by themey
+; Select it with: kvantummanager --set Themey
+
+[%General]
+author=themey
+comment=Colors generated by themey
+inherits=Kvantum
+
+[GeneralColors]
+window.color={background}
+base.color={background}
+alt.base.color={alt_base}
+button.color={button}
+light.color={light}
+mid.light.color={mid_light}
+dark.color={dark}
+mid.color={mid}
+highlight.color={blue}
+inactive.highlight.color={inactive_highlight}
+text.color={foreground}
+window.text.color={foreground}
+button.text.color={foreground}
+disabled.text.color={dimmed}
+tooltip.text.color={foreground}
+highlight.text.color={background}
+link.color={blue}
+link.visited.color={magenta}
+progress.indicator.text.color={background}
+",
+        background = bg,
+        foreground = fg,
+        alt_base = mix_hex(bg, fg, 0.05),
+        button = mix_hex(bg, fg, 0.1),
+        light = mix_hex(bg, fg, 0.3),
+        mid_light = mix_hex(bg, fg, 0.2),
+        dark = mix_hex(bg, "#000000", 0.4),
+        mid = mix_hex(bg, fg, 0.15),
+        blue = colors.normal.blue,
+        inactive_highlight = mix_hex(&colors.normal.blue, bg, 0.4),
+        dimmed = mix_hex(fg, bg, 0.5),
+        magenta = colors.normal.magenta,
+    )
+}
+
+fn generate_firefox_config(colors: &Colors, home: &str) -> TargetOutput {
+    let content = format!(
+        "/* Firefox colors - Generated by themey */
+/* Imported from userChrome.css */
+
+:root {{
+  --themey-bg: {background};
+  --themey-fg: {foreground};
+  --themey-surface: {surface};
+  --themey-overlay: {overlay};
+  --themey-muted: {bright_black};
+  --themey-accent: {blue};
+  --themey-urgent: {red};
+}}
+
+:root,
+#navigator-toolbox,
+#sidebar-box,
+#urlbar,
+panel {{
+  /* Window frame and toolbars */
+  --lwt-accent-color: var(--themey-bg) !important;
+  --lwt-text-color: var(--themey-fg) !important;
+  --toolbar-bgcolor: var(--themey-surface) !important;
+  --toolbar-color: var(--themey-fg) !important;
+  --toolbarbutton-icon-fill: var(--themey-fg) !important;
+  --chrome-content-separator-color: var(--themey-bg) !important;
+
+  /* Tabs */
+  --tab-selected-bgcolor: var(--themey-surface) !important;
+  --tab-selected-textcolor: var(--themey-fg) !important;
+  --lwt-tab-line-color: var(--themey-accent) !important;
+  --tab-loading-fill: var(--themey-accent) !important;
+  --tabs-border-color: var(--themey-overlay) !important;
+
+  /* URL bar */
+  --toolbar-field-background-color: var(--themey-bg) !important;
+  --toolbar-field-color: var(--themey-fg) !important;
+  --toolbar-field-border-color: var(--themey-overlay) !important;
+  --toolbar-field-focus-background-color: var(--themey-bg) !important;
+  --toolbar-field-focus-color: var(--themey-fg) !important;
+  --toolbar-field-focus-border-color: var(--themey-accent) !important;
+  --urlbarView-highlight-background: var(--themey-accent) !important;
+  --urlbarView-highlight-color: var(--themey-bg) !important;
+  --urlbarView-hover-background: var(--themey-overlay) !important;
+
+  /* Sidebar */
+  --sidebar-background-color: var(--themey-bg) !important;
+  --sidebar-text-color: var(--themey-fg) !important;
+  --sidebar-border-color: var(--themey-overlay) !important;
+  --lwt-sidebar-background-color: var(--themey-bg) !important;
+  --lwt-sidebar-text-color: var(--themey-fg) !important;
+  --lwt-sidebar-highlight-background-color: var(--themey-accent) !important;
+  --lwt-sidebar-highlight-text-color: var(--themey-bg) !important;
+
+  /* Menus and popups */
+  --arrowpanel-background: var(--themey-surface) !important;
+  --arrowpanel-color: var(--themey-fg) !important;
+  --arrowpanel-border-color: var(--themey-overlay) !important;
+  --focus-outline-color: var(--themey-accent) !important;
+}}
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        surface = mix_hex(&colors.special.background, &colors.special.foreground, 0.08),
+        overlay = mix_hex(&colors.special.background, &colors.special.foreground, 0.2),
+        bright_black = colors.bright.black,
+        blue = colors.normal.blue,
+        red = colors.normal.red,
+    );
+
+    let profiles: Vec<(String, String)> = [
+        ("Firefox", format!("{}/.mozilla/firefox", home)),
+        ("Thunderbird", format!("{}/.thunderbird", home)),
+    ]
+    .iter()
+    .flat_map(|(app, root)| {
+        mozilla_profiles(root).into_iter()
+            .map(move |(name, dir)| (format!("{} profile '{}'", app, name), dir))
+    })
+    .collect();
+
+    let mut files = Vec::new();
+    let mut notes = Vec::new();
+    if profiles.is_empty() {
+        notes.push("  ⚠ No Firefox or Thunderbird profiles found".to_string());
+    }
+
+    for (label, dir) in &profiles {
+        let chrome = format!("{}/chrome", dir);
+        files.push((format!("{}/themey.css", chrome), content.clone()));
+
+        // @import has to come before any other rule in userChrome.css
+        let user_chrome = format!("{}/userChrome.css", chrome);
+        let existing = std::fs::read_to_string(&user_chrome).unwrap_or_default();
+        if !existing.contains("themey.css") {
+            files.push((user_chrome, format!("@import \"themey.css\";\n{}", existing)));
+        }
+
+        // Firefox ignores userChrome.css unless this pref is set
+        let pref = "user_pref(\"toolkit.legacyUserProfileCustomizations.stylesheets\", true);";
+        let user_js = format!("{}/user.js", dir);
+        let prefs = std::fs::read_to_string(&user_js).unwrap_or_default();
+        if !prefs.contains("toolkit.legacyUserProfileCustomizations.stylesheets") {
+            let sep = if prefs.is_empty() || prefs.ends_with('\n') { "" } else { "\n" };
+            files.push((user_js, format!("{}{}{}\n", prefs, sep, pref)));
+        }
+
+        notes.push(format!("  ✓ Updated {}", label));
+    }
+
+    // Firefox reads userChrome.css on startup
+    TargetOutput { files, reload: None, notes }
+}
+
+// (name, directory) for each profile listed in a Mozilla profiles.ini
+fn mozilla_profiles(root: &str) -> Vec<(String, String)> {
+    let Ok(ini) = std::fs::read_to_string(format!("{}/profiles.ini", root)) else {
+        return Vec::new();
+    };
+
+    let mut profiles = Vec::new();
+    let mut section: Option<(String, String, bool)> = None;
+
+    let mut finish = |section: Option<(String, String, bool)>| {
+        if let Some((name, path, relative)) = section
+            && !path.is_empty()
+        {
+            let dir = if relative { format!("{}/{}", root, path) } else { path };
+            profiles.push((name, dir));
+        }
+    };
+
+    for line in ini.lines().map(str::trim) {
+        if line.starts_with('[') {
+            finish(section.take());
+            if line.starts_with("[Profile") {
+                section = Some((String::new(), String::new(), true));
+            }
+        } else if let Some((name, path, relative)) = section.as_mut()
+            && let Some((key, value)) = line.split_once('=')
+        {
+            match key {
+                "Name" => *name = value.to_string(),
+                "Path" => *path = value.to_string(),
+                "IsRelative" => *relative = value == "1",
+                _ => {}
+            }
+        }
+    }
+    finish(section);
+
+    profiles
+}
+
+fn generate_vesktop_config(colors: &Colors, home: &str) -> TargetOutput {
+    let path = format!("{}/.config/vesktop/themes/themey.theme.css", home);
+    let content = format!(
+        "/**
+ * @name Themey
+ * @author themey
+ * @description Colors generated by themey
+ */
+
+.theme-dark,
+.theme-light,
+.visual-refresh {{
+  --background-primary: {background};
+  --background-secondary: {surface};
+  --background-secondary-alt: {overlay};
+  --background-tertiary: {dark};
+  --background-floating: {dark};
+  --background-base-lowest: {dark};
+  --background-base-lower: {surface};
+  --background-base-low: {overlay};
+  --background-surface-high: {overlay};
+  --channeltextarea-background: {surface};
+  --background-modifier-hover: {hover};
+  --background-modifier-selected: {selected};
+  --background-modifier-accent: {selected};
+
+  --text-normal: {foreground};
+  --text-default: {foreground};
+  --text-muted: {muted};
+  --text-link: {blue};
+  --header-primary: {foreground};
+  --header-secondary: {muted};
+  --channels-default: {muted};
+  --interactive-normal: {muted};
+  --interactive-hover: {foreground};
+  --interactive-active: {foreground};
+  --interactive-muted: {bright_black};
+
+  --brand-500: {blue};
+  --brand-560: {blue};
+  --brand-experiment: {blue};
+  --status-danger: {red};
+  --status-warning: {yellow};
+  --status-positive: {green};
+}}
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        surface = mix_hex(&colors.special.background, &colors.special.foreground, 0.05),
+        overlay = mix_hex(&colors.special.background, &colors.special.foreground, 0.1),
+        dark = mix_hex(&colors.special.background, "#000000", 0.25),
+        hover = mix_hex(&colors.special.background, &colors.special.foreground, 0.12),
+        selected = mix_hex(&colors.special.background, &colors.special.foreground, 0.2),
+        muted = mix_hex(&colors.special.foreground, &colors.special.background, 0.3),
+        bright_black = colors.bright.black,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+    );
+
+    // Standalone Vencord keeps its themes in a separate directory
+    let vencord_dir = format!("{}/.config/Vencord/themes", home);
+    let mut files = vec![(path, content.clone())];
+    if std::path::Path::new(&vencord_dir).is_dir() {
+        files.push((format!("{}/themey.theme.css", vencord_dir), content));
+    }
+
+    // Enable it once under Settings > Themes; Vencord picks up changes live
+    TargetOutput { files, reload: None, notes: Vec::new() }
+}
+
+fn generate_obsidian_config(colors: &Colors, home: &str) -> TargetOutput {
+    let content = format!(
+        "/* Obsidian snippet - Generated by themey */
+/* Enable it under Settings > Appearance > CSS snippets */
+
+.theme-dark,
+.theme-light {{
+  --background-primary: {background};
+  --background-primary-alt: {surface};
+  --background-secondary: {surface};
+  --background-secondary-alt: {overlay};
+  --background-modifier-border: {overlay};
+  --background-modifier-hover: {hover};
+  --background-modifier-active-hover: {hover};
+
+  --text-normal: {foreground};
+  --text-muted: {muted};
+  --text-faint: {bright_black};
+  --text-accent: {blue};
+  --text-accent-hover: {cyan};
+  --text-on-accent: {background};
+  --text-selection: {selection};
+  --text-highlight-bg: {highlight};
+  --text-error: {red};
+  --text-success: {green};
+  --text-warning: {yellow};
+
+  --interactive-accent: {blue};
+  --interactive-accent-hover: {cyan};
+  --link-color: {blue};
+  --link-external-color: {cyan};
+  --tag-color: {magenta};
+
+  --h1-color: {red};
+  --h2-color: {yellow};
+  --h3-color: {green};
+  --h4-color: {cyan};
+  --h5-color: {blue};
+  --h6-color: {magenta};
+  --code-normal: {foreground};
+  --code-background: {surface};
+}}
+",
+        background = colors.special.background,
+        foreground = colors.special.foreground,
+        surface = mix_hex(&colors.special.background, &colors.special.foreground, 0.05),
+        overlay = mix_hex(&colors.special.background, &colors.special.foreground, 0.15),
+        hover = mix_hex(&colors.special.background, &colors.special.foreground, 0.1),
+        selection = mix_hex(&colors.special.background, &colors.normal.blue, 0.3),
+        highlight = mix_hex(&colors.special.background, &colors.normal.yellow, 0.3),
+        muted = mix_hex(&colors.special.foreground, &colors.special.background, 0.3),
+        bright_black = colors.bright.black,
+        red = colors.normal.red,
+        green = colors.normal.green,
+        yellow = colors.normal.yellow,
+        blue = colors.normal.blue,
+        magenta = colors.normal.magenta,
+        cyan = colors.normal.cyan,
+    );
+
+    let vaults = obsidian_vaults(home);
+    let mut notes: Vec<String> = vaults.iter()
+        .map(|vault| format!("  ✓ Updated vault {}", vault))
+        .collect();
+    if vaults.is_empty() {
+        notes.push("  ⚠ No Obsidian vaults found".to_string());
+    }
+
+    let files = vaults.iter()
+        .map(|vault| (format!("{}/.obsidian/snippets/themey.css", vault), content.clone()))
+        .collect();
+
+    // Obsidian reloads snippets when they change
+    TargetOutput { files, reload: None, notes }
+}
+
+// Vault directories listed in obsidian.json (native or Flatpak install)
+fn obsidian_vaults(home: &str) -> Vec<String> {
+    let configs = [
+        format!("{}/.config/obsidian/obsidian.json", home),
+        format!("{}/.var/app/md.obsidian.Obsidian/config/obsidian/obsidian.json", home),
+    ];
+
+    let mut vaults = Vec::new();
+    for config in &configs {
+        let Ok(json) = std::fs::read_to_string(config) else { continue };
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(&json) else { continue };
+        let Some(entries) = value["vaults"].as_object() else { continue };
+
+        for entry in entries.values() {
+            let Some(path) = entry["path"].as_str() else { continue };
+            if std::path::Path::new(path).is_dir() && !vaults.iter().any(|v| v == path) {
+                vaults.push(path.to_string());
+            }
+        }
+    }
+
+    vaults
+}
+
+fn generate_spicetify_config(colors: &Colors, home: &str) -> TargetOutput {
+    let path = format!("{}/.config/spicetify/Themes/Themey/color.ini", home);
+    let content = format!(
+        "; Spicetify colors - Generated by themey
+; Select it with: spicetify config current_theme Themey color_scheme Base
+
+[Base]
+text               = {foreground}
+subtext            = {muted}
+main               = {background}
+main-elevated      = {surface}
+highlight          = {overlay}
+highlight-elevated = {overlay}
+sidebar            = {dark}
+player             = {dark}
+card               = {surface}
+shadow             = {shadow}
+selected-row       = {foreground}
+button             = {blue}
+button-active      = {cyan}
+button-disabled    = {bright_black}
+tab-active         = {overlay}
+notification       = {blue}
+notification-error = {red}
+misc               = {bright_black}
+",
+        background = strip_hash(&colors.special.background),
+        foreground = strip_hash(&colors.special.foreground),
+        muted = strip_hash(&mix_hex(&colors.special.foreground, &colors.special.background, 0.3)),
+        surface = strip_hash(&mix_hex(&colors.special.background, &colors.special.foreground, 0.05)),
+        overlay = strip_hash(&mix_hex(&colors.special.background, &colors.special.foreground, 0.12)),
+        dark = strip_hash(&mix_hex(&colors.special.background, "#000000", 0.25)),
+        shadow = strip_hash(&mix_hex(&colors.special.background, "#000000", 0.6)),
+        bright_black = strip_hash(&colors.bright.black),
+        red = strip_hash(&colors.normal.red),
+        blue = strip_hash(&colors.normal.blue),
+        cyan = strip_hash(&colors.normal.cyan),
+    );
+
+    // Spicetify expects a user.css next to color.ini
+    let user_css = format!("{}/.config/spicetify/Themes/Themey/user.css", home);
+    let mut files = vec![(path, content)];
+    if !std::path::Path::new(&user_css).exists() {
+        files.push((user_css, "/* Spicetify theme - Generated by themey */\n".to_string()));
+    }
+
+    let reload = Some((
+        "spicetify".to_string(),
+        vec!["refresh".to_string()],
+        "Spotify".to_string(),
     ));
 
-    (path, content, reload)
+    TargetOutput { files, reload, notes: Vec::new() }
 }
 
-fn generate_hyprland_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
-    let path = format!("{}/.config/hypr/colors.conf", home);
+fn generate_hyprlock_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/hypr/hyprlock-colors.conf", home);
     let content = format!(
-        "# Hyprland colors - Generated by themey
+        "# Hyprlock colors - Generated by themey
+# Source this at the top of hyprlock.conf with:
+#   source = ~/.config/hypr/hyprlock-colors.conf
 $background = rgb({background})
 $foreground = rgb({foreground})
-$cursor = rgb({cursor})
 
 $black = rgb({black})
 $red = rgb({red})
@@ -155,25 +3115,18 @@ $blue = rgb({blue})
 $magenta = rgb({magenta})
 $cyan = rgb({cyan})
 $white = rgb({white})
-
 $bright_black = rgb({bright_black})
-$bright_red = rgb({bright_red})
-$bright_green = rgb({bright_green})
-$bright_yellow = rgb({bright_yellow})
-$bright_blue = rgb({bright_blue})
-$bright_magenta = rgb({bright_magenta})
-$bright_cyan = rgb({bright_cyan})
-$bright_white = rgb({bright_white})
 
-# Border colors
-general {{
-    col.active_border = $cyan $blue 45deg
-    col.inactive_border = $black
-}}
+# For input-field and label sections
+$outer_color = $blue
+$inner_color = $background
+$font_color = $foreground
+$check_color = $yellow
+$fail_color = $red
+$capslock_color = $magenta
 ",
         background = hex_to_rgb(&colors.special.background),
         foreground = hex_to_rgb(&colors.special.foreground),
-        cursor = hex_to_rgb(&colors.special.cursor),
         black = hex_to_rgb(&colors.normal.black),
         red = hex_to_rgb(&colors.normal.red),
         green = hex_to_rgb(&colors.normal.green),
@@ -183,192 +3136,260 @@ general {{
         cyan = hex_to_rgb(&colors.normal.cyan),
         white = hex_to_rgb(&colors.normal.white),
         bright_black = hex_to_rgb(&colors.bright.black),
-        bright_red = hex_to_rgb(&colors.bright.red),
-        bright_green = hex_to_rgb(&colors.bright.green),
-        bright_yellow = hex_to_rgb(&colors.bright.yellow),
-        bright_blue = hex_to_rgb(&colors.bright.blue),
-        bright_magenta = hex_to_rgb(&colors.bright.magenta),
-        bright_cyan = hex_to_rgb(&colors.bright.cyan),
-        bright_white = hex_to_rgb(&colors.bright.white),
     );
 
-    let reload = Some((
-        "hyprctl".to_string(),
-        vec!["reload".to_string()],
-        "hyprland".to_string(),
-    ));
-
-    (path, content, reload)
+    // Hyprlock reads its config each time it locks
+    (path, content, None)
 }
 
-fn generate_rofi_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
-    let path = format!("{}/.config/rofi/colors.rasi", home);
-    let content = format!(
-        "/* Rofi colors - Generated by themey */
-* {{
-    background: {background};
-    background-alt: {bright_black};
-    foreground: {foreground};
-    border: {blue};
-    cursor: {cursor};
+fn generate_swaylock_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/swaylock/config", home);
+    let block = format!(
+        "# themey colors start
+color={background}
+
+inside-color={background}
+inside-clear-color={background}
+inside-caps-lock-color={background}
+inside-ver-color={background}
+inside-wrong-color={background}
+
+ring-color={blue}
+ring-clear-color={green}
+ring-caps-lock-color={magenta}
+ring-ver-color={yellow}
+ring-wrong-color={red}
+
+line-color={background}
+line-clear-color={background}
+line-caps-lock-color={background}
+line-ver-color={background}
+line-wrong-color={background}
+
+key-hl-color={cyan}
+bs-hl-color={red}
+caps-lock-key-hl-color={cyan}
+caps-lock-bs-hl-color={red}
+
+text-color={foreground}
+text-clear-color={green}
+text-caps-lock-color={magenta}
+text-ver-color={yellow}
+text-wrong-color={red}
+
+separator-color=00000000
+layout-bg-color={background}
+layout-border-color={bright_black}
+layout-text-color={foreground}
+# themey colors end
+",
+        background = hex_to_rgb(&colors.special.background),
+        foreground = hex_to_rgb(&colors.special.foreground),
+        red = hex_to_rgb(&colors.normal.red),
+        green = hex_to_rgb(&colors.normal.green),
+        yellow = hex_to_rgb(&colors.normal.yellow),
+        blue = hex_to_rgb(&colors.normal.blue),
+        magenta = hex_to_rgb(&colors.normal.magenta),
+        cyan = hex_to_rgb(&colors.normal.cyan),
+        bright_black = hex_to_rgb(&colors.bright.black),
+    );
 
-    black: {black};
-    red: {red};
-    green: {green};
-    yellow: {yellow};
-    blue: {blue};
-    magenta: {magenta};
-    cyan: {cyan};
-    white: {white};
+    let existing = std::fs::read_to_string(&path).unwrap_or_default();
+    let content = splice_swaylock_colors(&existing, &block);
 
-    bright-black: {bright_black};
-    bright-red: {bright_red};
-    bright-green: {bright_green};
-    bright-yellow: {bright_yellow};
-    bright-blue: {bright_blue};
-    bright-magenta: {bright_magenta};
-    bright-cyan: {bright_cyan};
-    bright-white: {bright_white};
+    // swaylock reads its config each time it locks
+    (path, content, None)
+}
 
-    background-color: @background;
-    text-color: @foreground;
-    selected-normal-background: @blue;
-    selected-normal-foreground: @background;
-}}
+// Keep the user's other options, only the color keys are replaced
+fn splice_swaylock_colors(existing: &str, block: &str) -> String {
+    let keys: Vec<&str> = block.lines()
+        .filter_map(|l| l.split_once('=').map(|(k, _)| k))
+        .collect();
+    let kept: String = strip_marked(existing, "# themey colors start", "# themey colors end")
+        .lines()
+        .filter(|l| !keys.contains(&l.split('=').next().unwrap_or("").trim()))
+        .map(|l| format!("{}\n", l))
+        .collect();
+    match kept.trim_end() {
+        "" => block.to_string(),
+        kept => format!("{}\n\n{}", kept, block),
+    }
+}
+
+fn generate_fuzzel_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
+    let path = format!("{}/.config/fuzzel/fuzzel.ini", home);
+    // fuzzel wants RRGGBBAA
+    let rgba = |hex: &str| format!("{}ff", strip_hash(hex));
+    let section = format!(
+        "[colors]
+# Generated by themey
+background={background}
+text={foreground}
+prompt={blue}
+placeholder={bright_black}
+input={foreground}
+match={yellow}
+selection={selection}
+selection-text={foreground}
+selection-match={yellow}
+counter={bright_black}
+border={blue}
 ",
-        background = colors.special.background,
-        foreground = colors.special.foreground,
-        cursor = colors.special.cursor,
-        black = colors.normal.black,
-        red = colors.normal.red,
-        green = colors.normal.green,
-        yellow = colors.normal.yellow,
-        blue = colors.normal.blue,
-        magenta = colors.normal.magenta,
-        cyan = colors.normal.cyan,
-        white = colors.normal.white,
-        bright_black = colors.bright.black,
-        bright_red = colors.bright.red,
-        bright_green = colors.bright.green,
-        bright_yellow = colors.bright.yellow,
-        bright_blue = colors.bright.blue,
-        bright_magenta = colors.bright.magenta,
-        bright_cyan = colors.bright.cyan,
-        bright_white = colors.bright.white,
+        background = rgba(&colors.special.background),
+        foreground = rgba(&colors.special.foreground),
+        bright_black = rgba(&colors.bright.black),
+        yellow = rgba(&colors.normal.yellow),
+        blue = rgba(&colors.normal.blue),
+        selection = rgba(&mix_hex(&colors.special.background, &colors.special.foreground, 0.15)),
     );
 
+    let existing = std::fs::read_to_string(&path).unwrap_or_default();
+    let content = splice_fuzzel_colors(&existing, &section);
+
+    // fuzzel reads its config on every launch
     (path, content, None)
 }
 
-fn generate_dunst_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
-    let path = format!("{}/.config/dunst/dunstrc.d/colors.conf", home);
+// Replace only the [colors] section, keeping the rest of fuzzel.ini
+fn splice_fuzzel_colors(existing: &str, section: &str) -> String {
+    let mut in_colors = false;
+    let kept: String = existing.lines()
+        .filter(|l| {
+            if l.trim_start().starts_with('[') {
+                in_colors = l.trim() == "[colors]";
+            }
+            !in_colors
+        })
+        .map(|l| format!("{}\n", l))
+        .collect();
+    match kept.trim_end() {
+        "" => section.to_string(),
+        kept => format!("{}\n\n{}", kept, section),
+    }
+}
+
+fn generate_wofi_config(colors: &Colors, home: &str) -> TargetOutput {
+    let dir = format!("{}/.config/wofi", home);
     let content = format!(
-        "# Dunst colors - Generated by themey
-[urgency_low]
-background = \"{background}\"
-foreground = \"{foreground}\"
-frame_color = \"{blue}\"
+        "/* Wofi colors - Generated by themey */
+/* Imported from style.css, or pass it with: wofi --style ~/.config/wofi/themey.css */
+window {{
+    border-color: {blue};
+    background-color: {background};
+}}
 
-[urgency_normal]
-background = \"{background}\"
-foreground = \"{foreground}\"
-frame_color = \"{cyan}\"
+#input {{
+    color: {foreground};
+    background-color: {surface};
+}}
 
-[urgency_critical]
-background = \"{background}\"
-foreground = \"{foreground}\"
-frame_color = \"{red}\"
+#inner-box,
+#outer-box,
+#scroll {{
+    background-color: {background};
+}}
+
+#text {{
+    color: {foreground};
+}}
+
+#entry:selected {{
+    background-color: {selection};
+}}
+
+#entry:selected #text {{
+    color: {blue};
+}}
 ",
         background = colors.special.background,
         foreground = colors.special.foreground,
         blue = colors.normal.blue,
-        cyan = colors.normal.cyan,
-        red = colors.normal.red,
+        surface = mix_hex(&colors.special.background, &colors.special.foreground, 0.08),
+        selection = mix_hex(&colors.special.background, &colors.special.foreground, 0.15),
     );
+    let mut files = vec![(format!("{}/themey.css", dir), content)];
 
-    (path, content, None)
-}
-
-fn generate_foot_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
-    let path = format!("{}/.config/foot/colors.ini", home);
-    let content = format!(
-        "# Foot colors - Generated by themey
-[colors]
-foreground={foreground}
-background={background}
-cursor={background} {cursor}
-
-## Normal/regular colors (color palette 0-7)
-regular0={black}
-regular1={red}
-regular2={green}
-regular3={yellow}
-regular4={blue}
-regular5={magenta}
-regular6={cyan}
-regular7={white}
+    // Imported first, so the user's own rules in style.css still win
+    let style = format!("{}/style.css", dir);
+    let existing = std::fs::read_to_string(&style).unwrap_or_default();
+    if !existing.contains("themey.css") {
+        files.push((style, format!("@import url(\"themey.css\");\n{}", existing)));
+    }
 
-## Bright colors (color palette 8-15)
-bright0={bright_black}
-bright1={bright_red}
-bright2={bright_green}
-bright3={bright_yellow}
-bright4={bright_blue}
-bright5={bright_magenta}
-bright6={bright_cyan}
-bright7={bright_white}
-",
-        foreground = strip_hash(&colors.special.foreground),
-        background = strip_hash(&colors.special.background),
-        cursor = strip_hash(&colors.special.cursor),
-        black = strip_hash(&colors.normal.black),
-        red = strip_hash(&colors.normal.red),
-        green = strip_hash(&colors.normal.green),
-        yellow = strip_hash(&colors.normal.yellow),
-        blue = strip_hash(&colors.normal.blue),
-        magenta = strip_hash(&colors.normal.magenta),
-        cyan = strip_hash(&colors.normal.cyan),
-        white = strip_hash(&colors.normal.white),
-        bright_black = strip_hash(&colors.bright.black),
-        bright_red = strip_hash(&colors.bright.red),
-        bright_green = strip_hash(&colors.bright.green),
-        bright_yellow = strip_hash(&colors.bright.yellow),
-        bright_blue = strip_hash(&colors.bright.blue),
-        bright_magenta = strip_hash(&colors.bright.magenta),
-        bright_cyan = strip_hash(&colors.bright.cyan),
-        bright_white = strip_hash(&colors.bright.white),
-    );
+    // wofi reads its style on every launch
+    TargetOutput { files, reload: None, notes: Vec::new() }
+}
 
-    // Foot doesn't support hot-reloading, user needs to restart instances
-    (path, content, None)
+// Remove a block between two marker lines, markers included
+fn strip_marked(text: &str, start: &str, end: &str) -> String {
+    let mut text = text.to_string();
+    if let (Some(s), Some(e)) = (text.find(start), text.find(end))
+        && s < e
+    {
+        let line_start = text[..s].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = text[e..].find('\n').map_or(text.len(), |i| e + i + 1);
+        text.replace_range(line_start..line_end, "");
+    }
+    text
 }
 
 fn hex_to_rgb(hex: &str) -> String {
-    let hex = hex.trim_start_matches('#');
-    format!("{}", hex)
+    hex.trim_start_matches('#').to_string()
 }
 
 fn strip_hash(hex: &str) -> String {
     hex.trim_start_matches('#').to_string()
 }
 
-fn generate_gtk_config(colors: &Colors, home: &str) -> (String, String, ReloadCmd) {
-    // Create theme in ~/.themes/Themey/
-    let theme_name = "Themey";
-    let base_path = format!("{}/.themes/{}", home, theme_name);
+// 24-bit SGR color code, e.g. "38;2;122;162;247"
+fn truecolor(hex: &str) -> String {
+    let (r, g, b) = hex_to_rgb_tuple(hex);
+    format!("38;2;{};{};{}", r, g, b)
+}
 
-    // We'll return the gtk-3.0 path as the main path
-    // But we need to create both GTK3 and GTK4 directories
-    let gtk3_dir = format!("{}/gtk-3.0", base_path);
-    let gtk4_dir = format!("{}/gtk-4.0", base_path);
+// Blend two hex colors, `t` = 0.0 gives `a` and 1.0 gives `b`
+fn mix_hex(a: &str, b: &str, t: f64) -> String {
+    let (r1, g1, b1) = hex_to_rgb_tuple(a);
+    let (r2, g2, b2) = hex_to_rgb_tuple(b);
+    let mix = |x: u8, y: u8| (x as f64 + (y as f64 - x as f64) * t).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", mix(r1, r2), mix(g1, g2), mix(b1, b2))
+}
+
+// Nearest color in the xterm 256-color palette (6x6x6 cube or grayscale ramp)
+fn hex_to_xterm256(hex: &str) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let (r, g, b) = hex_to_rgb_tuple(hex);
+
+    let nearest = |c: u8| {
+        (0..6).min_by_key(|&i| (LEVELS[i] as i32 - c as i32).abs()).unwrap()
+    };
+    let dist = |(r2, g2, b2): (u8, u8, u8)| {
+        let (dr, dg, db) = (r as i32 - r2 as i32, g as i32 - g2 as i32, b as i32 - b2 as i32);
+        dr * dr + dg * dg + db * db
+    };
+
+    let (ri, gi, bi) = (nearest(r), nearest(g), nearest(b));
+    let cube = (LEVELS[ri], LEVELS[gi], LEVELS[bi]);
+
+    let avg = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_idx = ((avg.saturating_sub(8) + 5) / 10).min(23) as u8;
+    let gray_val = 8 + gray_idx * 10;
+
+    if dist((gray_val, gray_val, gray_val)) < dist(cube) {
+        232 + gray_idx
+    } else {
+        16 + 36 * ri as u8 + 6 * gi as u8 + bi as u8
+    }
+}
 
-    // Create directories
-    std::fs::create_dir_all(&gtk3_dir).ok();
-    std::fs::create_dir_all(&gtk4_dir).ok();
+fn generate_gtk_config(colors: &Colors, home: &str) -> TargetOutput {
+    // Create theme in ~/.themes/Themey/ with both GTK3 and GTK4 stylesheets
+    let theme_name = "Themey";
+    let base_path = format!("{}/.themes/{}", home, theme_name);
 
     // Also create index.theme file for the theme to be recognized
-    let index_content = format!(
-        "[Desktop Entry]
+    let index_content = "[Desktop Entry]
 Type=X-GNOME-Metatheme
 Name=Themey
 Comment=Theme generated by Themey
@@ -379,9 +3400,7 @@ GtkTheme=Themey
 MetacityTheme=Themey
 IconTheme=Adwaita
 CursorTheme=Adwaita
-"
-    );
-    std::fs::write(format!("{}/index.theme", base_path), index_content).ok();
+";
 
     let gtk_css = format!(
         "/* GTK theme - Generated by themey */
@@ -530,13 +3549,6 @@ notebook > header > tabs > tab:checked {{
         yellow = colors.normal.yellow,
     );
 
-    // Write GTK3 file
-    let gtk3_path = format!("{}/gtk.css", gtk3_dir);
-    std::fs::write(&gtk3_path, &gtk_css).ok();
-
-    // Write GTK4 file
-    let gtk4_path = format!("{}/gtk.css", gtk4_dir);
-    std::fs::write(&gtk4_path, &gtk_css).ok();
 
     // Apply the theme using gsettings
     let reload = Some((
@@ -552,7 +3564,37 @@ notebook > header > tabs > tab:checked {{
         "GTK".to_string(),
     ));
 
-    (gtk3_path, gtk_css, reload)
+    TargetOutput {
+        files: vec![
+            (format!("{}/index.theme", base_path), index_content.to_string()),
+            (format!("{}/gtk-3.0/gtk.css", base_path), gtk_css.clone()),
+            (format!("{}/gtk-4.0/gtk.css", base_path), gtk_css),
+        ],
+        reload,
+        notes: Vec::new(),
+    }
+}
+
+// Put back the gsettings the GTK reload overwrites
+fn gtk_revert() -> ReloadCmd {
+    let schema = "org.gnome.desktop.interface";
+    let theme = query("gsettings", &["get", schema, "gtk-theme"])?;
+    let scheme = query("gsettings", &["get", schema, "color-scheme"])?;
+
+    Some((
+        "sh".to_string(),
+        vec![
+            "-c".to_string(),
+            format!(
+                "gsettings set {schema} gtk-theme \"$1\" && gsettings set {schema} color-scheme \"$2\"",
+                schema = schema
+            ),
+            "sh".to_string(),
+            theme.trim().to_string(),
+            scheme.trim().to_string(),
+        ],
+        "GTK".to_string(),
+    ))
 }
 
 // Add this function to src/plugins/templates.rs
@@ -801,4 +3843,105 @@ vim.g.terminal_color_15 = colors.bright_white
     // Note: We don't auto-reload Neovim as it requires running :colorscheme themey
     // Users can add an autocmd in their config if they want auto-reloading
     (path, content, None)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PALETTE: &str = "# themey palette start\n[palettes.themey]\nblue = \"#0000ff\"\n# themey palette end\n";
+
+    fn starship(existing: &str) -> String {
+        inject_starship_palette(existing, PALETTE, "# themey palette start", "# themey palette end")
+    }
+
+    #[test]
+    fn starship_palette_into_empty_config() {
+        assert_eq!(starship(""), format!("palette = \"themey\"\n\n{}", PALETTE));
+    }
+
+    #[test]
+    fn starship_palette_keeps_user_palette() {
+        let existing = "palette = \"mine\"\n\n[palettes.mine]\nblue = \"#123456\"\n";
+        let out = starship(existing);
+        assert!(out.starts_with(existing.trim_end()));
+        assert!(out.ends_with(PALETTE));
+        assert_eq!(out.matches("palette = ").count(), 1);
+    }
+
+    #[test]
+    fn starship_palette_replaces_previous_block() {
+        let existing = "[character]\nsymbol = \">\"\n\n# themey palette start\n[palettes.themey]\nblue = \"#111111\"\n# themey palette end\n[git_branch]\nstyle = \"blue\"\n";
+        let out = starship(existing);
+        assert_eq!(out.matches("# themey palette start").count(), 1);
+        assert!(!out.contains("#111111"));
+        assert!(out.contains("[character]") && out.contains("[git_branch]"));
+        assert_eq!(starship(&out), out);
+    }
+
+    const THEME: &str = "# themey theme start\ntheme:\n  activeBorderColor:\n    - \"#0000ff\"\n# themey theme end";
+
+    #[test]
+    fn lazygit_theme_without_gui_section() {
+        let out = inject_lazygit_theme("git:\n  paging:\n    pager: delta\n", THEME).unwrap();
+        assert_eq!(
+            out,
+            "git:\n  paging:\n    pager: delta\ngui:\n  # themey theme start\n  theme:\n    activeBorderColor:\n      - \"#0000ff\"\n  # themey theme end\n"
+        );
+    }
+
+    #[test]
+    fn lazygit_theme_into_existing_gui_section() {
+        let existing = "gui:\n    nerdFontsVersion: \"3\"\n    showIcons: true\ngit:\n    autoFetch: false\n";
+        let out = inject_lazygit_theme(existing, THEME).unwrap();
+        assert_eq!(
+            out,
+            "gui:\n    # themey theme start\n    theme:\n      activeBorderColor:\n        - \"#0000ff\"\n    # themey theme end\n    nerdFontsVersion: \"3\"\n    showIcons: true\ngit:\n    autoFetch: false\n"
+        );
+    }
+
+    #[test]
+    fn lazygit_theme_skips_inline_gui_mapping() {
+        assert_eq!(inject_lazygit_theme("gui: { showIcons: true }\n", THEME), None);
+    }
+
+    #[test]
+    fn lazygit_theme_skips_user_theme() {
+        let existing = "gui:\n  theme:\n    activeBorderColor:\n      - red\n";
+        assert_eq!(inject_lazygit_theme(existing, THEME), None);
+    }
+
+    #[test]
+    fn lazygit_theme_reapplies_in_place() {
+        let existing = "gui:\n  showIcons: true\n";
+        let once = inject_lazygit_theme(existing, THEME).unwrap();
+        let other = THEME.replace("#0000ff", "#ff0000");
+        let twice = inject_lazygit_theme(&once, &other).unwrap();
+        assert_eq!(twice, once.replace("#0000ff", "#ff0000"));
+        assert_eq!(twice.matches("# themey theme start").count(), 1);
+    }
+
+    #[test]
+    fn fuzzel_colors_replace_only_their_section() {
+        let existing = "[main]\nfont=monospace:size=11\n\n[colors]\nbackground=000000ff\ntext=ffffffff\n\n[border]\nwidth=2\n";
+        let section = "[colors]\n# Generated by themey\nbackground=1a1b26ff\n";
+        let out = splice_fuzzel_colors(existing, section);
+        assert_eq!(out, format!("[main]\nfont=monospace:size=11\n\n[border]\nwidth=2\n\n{}", section));
+        assert_eq!(splice_fuzzel_colors(&out, section), out);
+    }
+
+    #[test]
+    fn fuzzel_colors_into_empty_config() {
+        let section = "[colors]\nbackground=1a1b26ff\n";
+        assert_eq!(splice_fuzzel_colors("", section), section);
+    }
+
+    #[test]
+    fn swaylock_colors_replace_only_color_keys() {
+        let existing = "ignore-empty-password\nring-color=ff0000\nindicator-radius=100\n";
+        let block = "# themey colors start\nring-color=7aa2f7\n# themey colors end\n";
+        let out = splice_swaylock_colors(existing, block);
+        assert_eq!(out, format!("ignore-empty-password\nindicator-radius=100\n\n{}", block));
+        assert_eq!(splice_swaylock_colors(&out, block), out);
+    }
+}
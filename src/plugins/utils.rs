@@ -6,6 +6,7 @@ use std::io;
 use std::fs;
 use std::env;
 use std::path::Path;
+use std::time::Duration;
 
 pub fn clone_pb(url: &str, p: &str) -> Repository {
     let pb = ProgressBar::new(0);
@@ -52,10 +53,10 @@ pub fn list_themes(p: &str) -> Vec<String> {
             let p = e.path();
             if p.is_dir() {
                 let m = p.join("metadata.toml");
-                if m.exists() {
-                    if let Some(s) = p.file_name().and_then(|x| x.to_str()) {
-                        v.push(s.to_string());
-                    }
+                if m.exists()
+                    && let Some(s) = p.file_name().and_then(|x| x.to_str())
+                {
+                    v.push(s.to_string());
                 }
             }
         }
@@ -63,6 +64,45 @@ pub fn list_themes(p: &str) -> Vec<String> {
     v
 }
 
+// Parse durations like "90", "30s", "5m" or "1h"
+pub fn parse_duration(s: &str) -> Result<Duration, Box<dyn std::error::Error>> {
+    let s = s.trim();
+    let (num, mult) = match s.chars().last() {
+        Some('s') => (&s[..s.len() - 1], 1),
+        Some('m') => (&s[..s.len() - 1], 60),
+        Some('h') => (&s[..s.len() - 1], 3600),
+        _ => (s, 1),
+    };
+    let n: u64 = num.parse().map_err(|_| format!("Invalid duration: {}", s))?;
+    let secs = n.checked_mul(mult).ok_or_else(|| format!("Duration too long: {}", s))?;
+    Ok(Duration::from_secs(secs))
+}
+
+// Escape text for embedding in SVG or HTML
+pub fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
 pub fn print_completions<G: Generator>(r#gen: G, cmd: &mut clap::Command) {
     generate(r#gen, cmd, cmd.get_name().to_string(), &mut io::stdout());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration_units() {
+        assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_duration("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_duration("1h").unwrap(), Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn parse_duration_rejects_bad_input() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("99999999999999999h").is_err());
+    }
+}